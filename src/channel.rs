@@ -0,0 +1,129 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Trace context propagation across channels.
+//!
+//! This module provides the `Traced<T>` wrapper, which bundles a value with the OpenTelemetry
+//! context captured by the producer, so the consumer of a channel can continue the same trace
+//! instead of creating orphaned spans.
+
+use opentelemetry::{Context, ContextGuard};
+
+/// A value bundled with the OpenTelemetry context of its producer.
+#[derive(Debug, Clone)]
+pub struct Traced<T> {
+    value: T,
+    ctx: Context,
+}
+
+impl<T> Traced<T> {
+    /// Creates a new `Traced` capturing the current context.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to send across the channel
+    ///
+    /// # Returns
+    ///
+    /// A new `Traced` instance carrying `Context::current()`
+    pub fn new(value: T) -> Traced<T> {
+        Traced::with_context(value, Context::current())
+    }
+
+    /// Creates a new `Traced` carrying the provided context.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to send across the channel
+    /// * `ctx` - The context to propagate with the value
+    ///
+    /// # Returns
+    ///
+    /// A new `Traced` instance
+    pub fn with_context(value: T, ctx: Context) -> Traced<T> {
+        Traced { value, ctx }
+    }
+
+    /// Returns the context captured by the producer.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Splits the wrapper into the value and the captured context.
+    ///
+    /// Prefer this over `attach` in async code, passing the context to
+    /// `opentelemetry::trace::FutureExt::with_context`, since the guard returned by
+    /// `attach` must not be held across `.await` points.
+    pub fn into_parts(self) -> (T, Context) {
+        (self.value, self.ctx)
+    }
+
+    /// Attaches the captured context as the current context and returns the value.
+    ///
+    /// The context stays current until the returned guard is dropped.
+    pub fn attach(self) -> (T, ContextGuard) {
+        let guard = self.ctx.attach();
+        (self.value, guard)
+    }
+}
+
+/// Sends a value through a tokio mpsc channel, capturing the current context.
+///
+/// # Arguments
+///
+/// * `tx` - The sender side of the channel
+/// * `value` - The value to send
+///
+/// # Returns
+///
+/// * `Ok(())` if the value was sent
+/// * `Err(TracesError::ChannelClosedError)` if the receiver was dropped
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use traces::channel::{self, Traced};
+/// use tokio::sync::mpsc;
+///
+/// async fn produce(tx: mpsc::Sender<Traced<u64>>) {
+///     channel::send(&tx, 42).await.expect("receiver dropped");
+/// }
+/// ```
+#[cfg(feature = "channel")]
+pub async fn send<T>(
+    tx: &tokio::sync::mpsc::Sender<Traced<T>>,
+    value: T,
+) -> Result<(), crate::errors::TracesError> {
+    tx.send(Traced::new(value))
+        .await
+        .map_err(|_| crate::errors::TracesError::ChannelClosedError)
+}
+
+/// Receives a value from a tokio mpsc channel along with the producer's context.
+///
+/// # Arguments
+///
+/// * `rx` - The receiver side of the channel
+///
+/// # Returns
+///
+/// The value and the producer's context, or `None` if the channel is closed
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::trace::FutureExt;
+/// use traces::channel::{self, Traced};
+/// use tokio::sync::mpsc;
+///
+/// async fn consume(mut rx: mpsc::Receiver<Traced<u64>>) {
+///     while let Some((value, ctx)) = channel::recv(&mut rx).await {
+///         async move { println!("{value}") }.with_context(ctx).await;
+///     }
+/// }
+/// ```
+#[cfg(feature = "channel")]
+pub async fn recv<T>(rx: &mut tokio::sync::mpsc::Receiver<Traced<T>>) -> Option<(T, Context)> {
+    rx.recv().await.map(Traced::into_parts)
+}
@@ -9,6 +9,12 @@
 use thiserror::Error;
 
 /// Errors that can occur when working with distributed traces.
+///
+/// Errors wrapping an underlying failure carry its message rather than the source error,
+/// so `TracesError` stays comparable with `PartialEq`/`Eq`, comparing on the message, which
+/// `std::io::Error` and boxed sources are not. The message includes the context of the
+/// failure, e.g. the file path, so the `Display` output alone is actionable and
+/// `Error::source` returns `None`.
 #[derive(Error, Debug, PartialEq, Eq)]
 pub enum TracesError {
     /// An unexpected internal error occurred.
@@ -24,6 +30,46 @@ pub enum TracesError {
     ConversionError,
 
     /// Failed to create the OpenTelemetry exporter provider.
-    #[error("failure to create the exporter provider")]
-    ExporterProviderError,
+    ///
+    /// Holds the message of the exporter build error, e.g. a connection or TLS failure.
+    #[error("failure to create the exporter provider: {0}")]
+    ExporterProviderError(String),
+
+    /// The channel used to propagate a traced value is closed.
+    #[error("the channel is closed")]
+    ChannelClosedError,
+
+    /// Failed to flush the pending spans of the tracer provider.
+    #[error("failure to flush the tracer provider")]
+    ProviderFlushError,
+
+    /// The pending spans were not flushed within the timeout.
+    #[error("the tracer provider flush timed out")]
+    FlushTimeoutError,
+
+    /// Failed to shut down the tracer provider.
+    #[error("failure to shutdown the tracer provider")]
+    ProviderShutdownError,
+
+    /// The tracer provider was already shut down.
+    #[error("the tracer provider is already shutdown")]
+    AlreadyShutdownError,
+
+    /// The tracing configuration is invalid.
+    #[error("invalid configuration: {0}")]
+    InvalidConfigError(String),
+
+    /// A file of the exporter could not be opened, read or written.
+    ///
+    /// Holds the path and the message of the underlying I/O error.
+    #[error("I/O error: {0}")]
+    IoError(String),
+
+    /// The TLS files of the exporter are missing or not PEM encoded.
+    #[error("invalid TLS configuration: {0}")]
+    TlsConfigError(String),
+
+    /// The collector could not be reached.
+    #[error("the collector is unreachable: {0}")]
+    CollectorUnreachableError(String),
 }
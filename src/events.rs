@@ -0,0 +1,127 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Bridge from `tracing` events to span events.
+//!
+//! This module provides a `tracing-subscriber` layer that records each `tracing` event,
+//! such as `tracing::info!`, as an event of the current OpenTelemetry span, so logs show
+//! up inline on the span timeline in the trace viewer.
+
+use opentelemetry::{Context, KeyValue, trace::TraceContextExt};
+use std::fmt::Debug;
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_subscriber::layer::{self, Layer};
+
+/// A layer recording `tracing` events as events of the current OpenTelemetry span.
+///
+/// The span event is named after the event message and carries the `level` and `target`
+/// of the event along with its fields as attributes. Events emitted while the current span
+/// is not recording are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::{global, trace::SpanKind};
+/// use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+/// use traces::{events::SpanEventsLayer, helpers};
+///
+/// fn main() {
+///     tracing_subscriber::registry().with(SpanEventsLayer::new()).init();
+///
+///     let tracer = global::tracer("my_service");
+///     let _guard = helpers::scoped(&tracer, SpanKind::Server, "handle");
+///     // Recorded as an event of the `handle` span
+///     tracing::info!(user_id = 42, "user loaded");
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpanEventsLayer;
+
+impl SpanEventsLayer {
+    /// Creates a new `SpanEventsLayer`.
+    ///
+    /// # Returns
+    ///
+    /// A new `SpanEventsLayer` instance
+    pub fn new() -> SpanEventsLayer {
+        SpanEventsLayer
+    }
+}
+
+impl<S: Subscriber> Layer<S> for SpanEventsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: layer::Context<'_, S>) {
+        let cx = Context::current();
+        let span = cx.span();
+
+        if !span.is_recording() {
+            return;
+        }
+
+        let metadata = event.metadata();
+        let mut visitor = EventVisitor {
+            message: None,
+            attributes: vec![
+                KeyValue::new("level", metadata.level().as_str()),
+                KeyValue::new("target", metadata.target()),
+            ],
+        };
+        event.record(&mut visitor);
+
+        let name = visitor
+            .message
+            .unwrap_or_else(|| metadata.name().to_owned());
+
+        span.add_event(name, visitor.attributes);
+    }
+}
+
+/// Collects the message and fields of an event.
+struct EventVisitor {
+    message: Option<String>,
+    attributes: Vec<KeyValue>,
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_owned());
+        } else {
+            self.attributes
+                .push(KeyValue::new(field.name(), value.to_owned()));
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.attributes.push(KeyValue::new(field.name(), value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.attributes.push(KeyValue::new(field.name(), value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match i64::try_from(value) {
+            Ok(value) => self.attributes.push(KeyValue::new(field.name(), value)),
+            Err(_) => self
+                .attributes
+                .push(KeyValue::new(field.name(), value.to_string())),
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.attributes.push(KeyValue::new(field.name(), value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        } else {
+            self.attributes
+                .push(KeyValue::new(field.name(), format!("{value:?}")));
+        }
+    }
+}
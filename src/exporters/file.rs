@@ -0,0 +1,358 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! File exporter implementation.
+//!
+//! This module provides functionality to export trace data to a local file as line-delimited
+//! JSON, one finished span per line. This exporter is intended for offline capture, e.g.
+//! debugging in air-gapped environments where neither a collector nor the console is
+//! available. The file is rotated by size into `<file>.1`, `<file>.2`, ... up to
+//! `<file>.5`, the oldest file being discarded.
+
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::{json, provider_builder, sampler},
+    processors, propagation,
+};
+use configs::{app::AppConfigs, otlp::OTLPConfigs};
+use opentelemetry::global;
+use opentelemetry_sdk::{
+    Resource,
+    error::{OTelSdkError, OTelSdkResult},
+    trace::{IdGenerator, RandomIdGenerator, SdkTracerProvider, SpanData, SpanExporter},
+};
+use serde_json::{Map, Value as Json};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+use tracing::{error, info};
+
+/// Number of rotated files kept next to the active file.
+const MAX_ROTATED_FILES: u32 = 5;
+
+/// Installs the file exporter for OpenTelemetry tracing.
+///
+/// Spans are batched and appended to `path` as line-delimited JSON. The file is flushed
+/// after each batch, so a crash loses at most the spans not yet exported.
+///
+/// # Arguments
+///
+/// * `path` - The file spans are written to, created if missing
+/// * `max_bytes` - The size from which the file is rotated, or `None` to never rotate
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError)` if the file cannot be opened or installation fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use traces::exporters::file;
+///
+/// fn main() {
+///     let provider = file::install(Path::new("/var/log/app/traces.jsonl"), Some(10 * 1024 * 1024))
+///         .expect("Failed to install file exporter");
+/// }
+/// ```
+pub fn install(path: &Path, max_bytes: Option<u64>) -> Result<SdkTracerProvider, TracesError> {
+    install_with_id_generator(path, max_bytes, RandomIdGenerator::default())
+}
+
+/// Installs the file exporter using a custom trace and span ID generator.
+///
+/// The generator is wrapped in a `ValidatedIdGenerator` so invalid identifiers
+/// are never emitted. See `exporters::id_generator` for guidance on implementing one.
+///
+/// # Arguments
+///
+/// * `path` - The file spans are written to, created if missing
+/// * `max_bytes` - The size from which the file is rotated, or `None` to never rotate
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError::IoError)` if the file cannot be opened
+/// * `Err(TracesError)` if installation fails
+pub fn install_with_id_generator<G: IdGenerator + 'static>(
+    path: &Path,
+    max_bytes: Option<u64>,
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
+    let app_cfgs = AppConfigs::new();
+    let otlp_cfgs = OTLPConfigs::new();
+    let traces_cfgs = TracesConfigs::new();
+
+    let exporter = FileExporter::new(path, max_bytes).map_err(|err| {
+        error!(
+            error = err.to_string(),
+            path = %path.display(),
+            "failure to open the traces file"
+        );
+        TracesError::IoError(format!("{}: {err}", path.display()))
+    })?;
+
+    let (builder, ratio_sampler) =
+        provider_builder(id_generator, &app_cfgs, &otlp_cfgs, &traces_cfgs);
+
+    let builder = processors::with_exporting_processor(
+        builder,
+        processors::batch::batch_processor(exporter, &traces_cfgs)?,
+        &traces_cfgs,
+    );
+
+    let provider = processors::with_processors(builder, &traces_cfgs).build();
+
+    global::set_tracer_provider(provider.clone());
+    sampler::register_ratio_sampler(ratio_sampler);
+    propagation::install(&traces_cfgs.propagators);
+
+    info!("traces::install file tracer installed");
+
+    Ok(provider)
+}
+
+/// The open file and its current size.
+#[derive(Debug)]
+struct Output {
+    writer: Option<BufWriter<File>>,
+    size: u64,
+}
+
+/// A span exporter writing spans to a size-rotated file as line-delimited JSON.
+#[derive(Debug)]
+pub struct FileExporter {
+    path: PathBuf,
+    max_bytes: Option<u64>,
+    output: Mutex<Output>,
+    resource: Map<String, Json>,
+}
+
+impl FileExporter {
+    /// Creates a new `FileExporter` appending to the provided file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file spans are written to, created if missing
+    /// * `max_bytes` - The size from which the file is rotated, or `None` to never rotate
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FileExporter)` if the file can be opened
+    /// * `Err(std::io::Error)` otherwise
+    pub fn new(path: &Path, max_bytes: Option<u64>) -> std::io::Result<FileExporter> {
+        let file = open(path)?;
+        let size = file.metadata()?.len();
+
+        Ok(FileExporter {
+            path: path.to_path_buf(),
+            max_bytes,
+            output: Mutex::new(Output {
+                writer: Some(BufWriter::new(file)),
+                size,
+            }),
+            resource: Map::new(),
+        })
+    }
+
+    /// Writes a batch of spans, rotating the file when it exceeds the maximum size.
+    fn write(&self, batch: Vec<SpanData>) -> std::io::Result<()> {
+        let mut output = self
+            .output
+            .lock()
+            .map_err(|err| std::io::Error::other(err.to_string()))?;
+
+        if output.writer.is_none() {
+            return Err(std::io::Error::other("the file exporter is shutdown"));
+        }
+
+        for span in batch {
+            let mut line = serde_json::to_vec(&json::span_to_json(&span, Some(&self.resource)))?;
+            line.push(b'\n');
+
+            if let Some(max_bytes) = self.max_bytes
+                && output.size > 0
+                && output.size + line.len() as u64 > max_bytes
+            {
+                self.rotate(&mut output)?;
+            }
+
+            let Some(writer) = output.writer.as_mut() else {
+                return Err(std::io::Error::other("the file exporter is shutdown"));
+            };
+            writer.write_all(&line)?;
+            output.size += line.len() as u64;
+        }
+
+        match output.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Shifts the rotated files, moves the active file to `<file>.1` and reopens it.
+    ///
+    /// The writer is only replaced once the active file is reopened, so on failure spans
+    /// keep being written to the previous file and the rotation is retried on the next batch.
+    fn rotate(&self, output: &mut Output) -> std::io::Result<()> {
+        if let Some(writer) = output.writer.as_mut() {
+            writer.flush()?;
+        }
+
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let from = rotated(&self.path, index);
+            if from.exists() {
+                fs::rename(&from, rotated(&self.path, index + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated(&self.path, 1))?;
+
+        output.writer = Some(BufWriter::new(open(&self.path)?));
+        output.size = 0;
+
+        Ok(())
+    }
+}
+
+impl SpanExporter for FileExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        self.write(batch)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn shutdown_with_timeout(&mut self, _timeout: Duration) -> OTelSdkResult {
+        let mut output = self
+            .output
+            .lock()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?;
+
+        match output.writer.take() {
+            Some(mut writer) => writer
+                .flush()
+                .map_err(|err| OTelSdkError::InternalFailure(err.to_string())),
+            None => Err(OTelSdkError::AlreadyShutdown),
+        }
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = json::resource_to_json(resource);
+    }
+}
+
+/// Opens a file in append mode, creating it if missing.
+fn open(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Returns the path of the rotated file with the provided index.
+fn rotated(path: &Path, index: u32) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{index}"));
+    PathBuf::from(rotated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers, provider, test_utils};
+    use opentelemetry::trace::{SpanKind, TraceContextExt, Tracer, TracerProvider};
+
+    /// Returns a path in the temporary directory, removing the files left by a previous run.
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("traces-{}-{name}.jsonl", std::process::id()));
+        remove(&path);
+        path
+    }
+
+    /// Removes a traces file and its rotated files.
+    fn remove(path: &Path) {
+        let _ = fs::remove_file(path);
+        for index in 1..=MAX_ROTATED_FILES {
+            let _ = fs::remove_file(rotated(path, index));
+            let _ = fs::remove_dir_all(rotated(path, index));
+        }
+    }
+
+    /// Parses the lines of a traces file.
+    fn lines(path: &Path) -> Vec<Json> {
+        fs::read_to_string(path)
+            .expect("read traces file")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid JSON line"))
+            .collect()
+    }
+
+    /// Builds a local provider exporting each span to the file when it ends.
+    fn file_provider(path: &Path, max_bytes: Option<u64>) -> SdkTracerProvider {
+        SdkTracerProvider::builder()
+            .with_simple_exporter(FileExporter::new(path, max_bytes).expect("open traces file"))
+            .build()
+    }
+
+    #[test]
+    fn writes_a_json_line_per_span() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_SAMPLING_RATIO", "1.0")]);
+        let path = temp_path("install");
+        let tracer_provider = install(&path, None).expect("install");
+
+        let ctx = helpers::ctx(&provider::tracer(), SpanKind::Internal, "offline");
+        ctx.span().end();
+        tracer_provider.force_flush().expect("flush");
+
+        let lines = lines(&path);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["name"], "offline");
+        assert_eq!(lines[0]["trace_id"], helpers::trace_id(&ctx));
+
+        tracer_provider.shutdown().expect("shutdown");
+        remove(&path);
+    }
+
+    #[test]
+    fn rotates_the_file_by_size() {
+        let path = temp_path("rotation");
+        let tracer_provider = file_provider(&path, Some(1));
+        let tracer = tracer_provider.tracer("file");
+
+        for name in ["first", "second", "third"] {
+            tracer.in_span(name, |_| {});
+        }
+
+        assert_eq!(lines(&rotated(&path, 2))[0]["name"], "first");
+        assert_eq!(lines(&rotated(&path, 1))[0]["name"], "second");
+        assert_eq!(lines(&path)[0]["name"], "third");
+
+        tracer_provider.shutdown().expect("shutdown");
+        remove(&path);
+    }
+
+    #[test]
+    fn keeps_writing_after_a_failed_rotation() {
+        let path = temp_path("failed-rotation");
+        let tracer_provider = file_provider(&path, Some(1));
+        let tracer = tracer_provider.tracer("file");
+
+        tracer.in_span("first", |_| {});
+        // A directory in place of `<file>.5` makes shifting `<file>.4` fail
+        fs::write(rotated(&path, 4), "").expect("create rotated file");
+        fs::create_dir_all(rotated(&path, 5).join("occupied")).expect("create directory");
+        tracer.in_span("lost", |_| {});
+        fs::remove_dir_all(rotated(&path, 5)).expect("remove directory");
+        tracer.in_span("third", |_| {});
+
+        assert_eq!(lines(&rotated(&path, 1))[0]["name"], "first");
+        assert_eq!(lines(&path)[0]["name"], "third");
+
+        tracer_provider.shutdown().expect("shutdown");
+        remove(&path);
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Trace and span ID generation.
+//!
+//! This module provides helpers to plug custom `IdGenerator` implementations into the
+//! tracer provider while keeping the generated identifiers valid per the W3C Trace Context
+//! specification.
+//!
+//! Custom generators are useful to embed routing information in the identifiers, for example
+//! encoding a shard in the high bits of the span ID:
+//!
+//! ```rust
+//! use opentelemetry::trace::{SpanId, TraceId};
+//! use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator};
+//!
+//! #[derive(Debug)]
+//! struct ShardIdGenerator {
+//!     shard: u8,
+//!     random: RandomIdGenerator,
+//! }
+//!
+//! impl IdGenerator for ShardIdGenerator {
+//!     fn new_trace_id(&self) -> TraceId {
+//!         self.random.new_trace_id()
+//!     }
+//!
+//!     fn new_span_id(&self) -> SpanId {
+//!         // Keep 56 random bits so span IDs stay unique within a trace
+//!         let mut bytes = self.random.new_span_id().to_bytes();
+//!         bytes[0] = self.shard;
+//!         SpanId::from_bytes(bytes)
+//!     }
+//! }
+//! ```
+//!
+//! Generators should keep enough random bits to avoid collisions and must never return
+//! the all-zero identifiers, which the specification reserves as invalid. Generators
+//! installed through this crate are wrapped in a `ValidatedIdGenerator` that replaces
+//! invalid identifiers with random ones.
+//!
+//! Setting `TRACES_TRACE_ID_BITS` to `64` wraps the generator in a `Truncated64IdGenerator`,
+//! zeroing the high 64 bits of every trace ID for backends limited to 64-bit trace IDs.
+
+use crate::errors::TracesError;
+use opentelemetry::trace::{SpanId, TraceId};
+use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator};
+#[cfg(feature = "testing")]
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{str::FromStr, sync::Arc};
+use tracing::warn;
+
+/// The trace and span ID generator used by `provider::install`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdGeneratorKind {
+    /// Random identifiers, as per the W3C Trace Context specification.
+    #[default]
+    Random,
+    /// AWS X-Ray compatible identifiers, whose first 4 bytes are the trace start time in
+    /// seconds, requires the `xray` feature. Pair it with the X-Ray propagator, as X-Ray
+    /// rejects trace IDs without a valid timestamp.
+    XRay,
+}
+
+impl FromStr for IdGeneratorKind {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "random" => Ok(IdGeneratorKind::Random),
+            "xray" => Ok(IdGeneratorKind::XRay),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// The number of significant bits of the generated trace IDs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceIdBits {
+    /// 64-bit trace IDs, whose high 64 bits are zeroed, for interoperability with backends
+    /// and propagation formats limited to 64-bit trace IDs, such as older Datadog, Zipkin
+    /// or Jaeger deployments. By the birthday bound, collisions become likely after a few
+    /// billion traces, where 128-bit IDs practically never collide.
+    Bits64,
+    /// 128-bit trace IDs, as per the W3C Trace Context specification.
+    #[default]
+    Bits128,
+}
+
+impl FromStr for TraceIdBits {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "64" => Ok(TraceIdBits::Bits64),
+            "128" => Ok(TraceIdBits::Bits128),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// An `IdGenerator` wrapper producing 64-bit compatible trace IDs.
+///
+/// The high 64 bits of the trace IDs returned by the wrapped generator are zeroed, so the
+/// IDs survive a round trip through 64-bit systems unchanged. Whenever the remaining low
+/// bits are all zero, random low bits are used instead to keep the trace ID valid. Span IDs
+/// are returned unchanged.
+#[derive(Debug)]
+pub struct Truncated64IdGenerator<G> {
+    inner: G,
+    fallback: RandomIdGenerator,
+}
+
+impl<G: IdGenerator> Truncated64IdGenerator<G> {
+    /// Creates a new `Truncated64IdGenerator` wrapping the provided generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The ID generator whose trace IDs are truncated
+    ///
+    /// # Returns
+    ///
+    /// A new `Truncated64IdGenerator` instance
+    pub fn new(inner: G) -> Truncated64IdGenerator<G> {
+        Truncated64IdGenerator {
+            inner,
+            fallback: RandomIdGenerator::default(),
+        }
+    }
+}
+
+impl<G: IdGenerator> IdGenerator for Truncated64IdGenerator<G> {
+    fn new_trace_id(&self) -> TraceId {
+        let mut low = truncate(self.inner.new_trace_id());
+        while low == 0 {
+            low = truncate(self.fallback.new_trace_id());
+        }
+
+        TraceId::from_bytes(u128::from(low).to_be_bytes())
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        self.inner.new_span_id()
+    }
+}
+
+/// Returns the low 64 bits of a trace ID.
+fn truncate(id: TraceId) -> u64 {
+    u128::from_be_bytes(id.to_bytes()) as u64
+}
+
+/// An `IdGenerator` wrapper that guarantees spec-compliant identifiers.
+///
+/// Whenever the wrapped generator returns an invalid (all-zero) trace or span ID,
+/// a random identifier is returned instead.
+#[derive(Debug)]
+pub struct ValidatedIdGenerator<G> {
+    inner: G,
+    fallback: RandomIdGenerator,
+}
+
+impl<G: IdGenerator> ValidatedIdGenerator<G> {
+    /// Creates a new `ValidatedIdGenerator` wrapping the provided generator.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The custom ID generator
+    ///
+    /// # Returns
+    ///
+    /// A new `ValidatedIdGenerator` instance
+    pub fn new(inner: G) -> ValidatedIdGenerator<G> {
+        ValidatedIdGenerator {
+            inner,
+            fallback: RandomIdGenerator::default(),
+        }
+    }
+}
+
+impl<G: IdGenerator> IdGenerator for ValidatedIdGenerator<G> {
+    fn new_trace_id(&self) -> TraceId {
+        let id = self.inner.new_trace_id();
+        if id == TraceId::INVALID {
+            warn!("custom id generator returned an invalid trace id, using a random one");
+            return self.fallback.new_trace_id();
+        }
+
+        id
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        let id = self.inner.new_span_id();
+        if id == SpanId::INVALID {
+            warn!("custom id generator returned an invalid span id, using a random one");
+            return self.fallback.new_span_id();
+        }
+
+        id
+    }
+}
+
+/// An `IdGenerator` shared behind an `Arc`, so a builder can build several providers.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedIdGenerator(pub(crate) Arc<dyn IdGenerator>);
+
+impl IdGenerator for SharedIdGenerator {
+    fn new_trace_id(&self) -> TraceId {
+        self.0.new_trace_id()
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        self.0.new_span_id()
+    }
+}
+
+/// Returns the generator selected by `TRACES_ID_GENERATOR`.
+///
+/// Without the `xray` feature the X-Ray generator falls back to random IDs, with a warning.
+pub(crate) fn configured(kind: IdGeneratorKind) -> SharedIdGenerator {
+    match kind {
+        IdGeneratorKind::Random => SharedIdGenerator(Arc::new(RandomIdGenerator::default())),
+        #[cfg(feature = "xray")]
+        IdGeneratorKind::XRay => SharedIdGenerator(Arc::new(
+            opentelemetry_aws::trace::XrayIdGenerator::default(),
+        )),
+        #[cfg(not(feature = "xray"))]
+        IdGeneratorKind::XRay => {
+            warn!("the xray id generator requires the xray feature, using random ids");
+            SharedIdGenerator(Arc::new(RandomIdGenerator::default()))
+        }
+    }
+}
+
+/// A deterministic `IdGenerator` producing the same sequence of identifiers for a seed.
+///
+/// Intended for tests asserting exact trace and span IDs; the identifiers are not random
+/// and must not be used in production.
+///
+/// # Examples
+///
+/// ```rust
+/// use opentelemetry_sdk::trace::IdGenerator;
+/// use traces::exporters::id_generator::SeededIdGenerator;
+///
+/// let first = SeededIdGenerator::new(42);
+/// let second = SeededIdGenerator::new(42);
+/// assert_eq!(first.new_trace_id(), second.new_trace_id());
+/// ```
+#[cfg(feature = "testing")]
+#[derive(Debug)]
+pub struct SeededIdGenerator {
+    state: AtomicU64,
+}
+
+#[cfg(feature = "testing")]
+impl SeededIdGenerator {
+    /// Creates a new `SeededIdGenerator` starting from the provided seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the identifier sequence
+    ///
+    /// # Returns
+    ///
+    /// A new `SeededIdGenerator` instance
+    pub fn new(seed: u64) -> SeededIdGenerator {
+        SeededIdGenerator {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// Returns the next value of the sequence, using the SplitMix64 generator.
+    fn next(&self) -> u64 {
+        let mut z = self
+            .state
+            .fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed)
+            .wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(feature = "testing")]
+impl IdGenerator for SeededIdGenerator {
+    fn new_trace_id(&self) -> TraceId {
+        let high = u128::from(self.next()) << 64;
+        TraceId::from_bytes((high | u128::from(self.next())).to_be_bytes())
+    }
+
+    fn new_span_id(&self) -> SpanId {
+        SpanId::from_bytes(self.next().to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::TracesConfigs, helpers, test_utils};
+    use configs::{app::AppConfigs, otlp::OTLPConfigs};
+    use opentelemetry::trace::SpanKind;
+    use opentelemetry_sdk::trace::Sampler;
+
+    #[cfg(feature = "xray")]
+    #[test]
+    fn configured_xray_generator_prefixes_the_start_time() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before the epoch")
+            .as_secs();
+        let bytes = configured(IdGeneratorKind::XRay).new_trace_id().to_bytes();
+        let seconds = u64::from(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]));
+
+        assert!(now.abs_diff(seconds) <= 60);
+    }
+
+    #[test]
+    fn configured_random_generator_returns_valid_ids() {
+        let generator = configured(IdGeneratorKind::Random);
+
+        assert_ne!(generator.new_trace_id(), TraceId::INVALID);
+        assert_ne!(generator.new_span_id(), SpanId::INVALID);
+    }
+
+    /// Returns the high 64 bits of a trace ID.
+    fn high_bits(id: TraceId) -> u64 {
+        (u128::from_be_bytes(id.to_bytes()) >> 64) as u64
+    }
+
+    #[test]
+    fn zeroes_the_high_64_bits_of_trace_ids() {
+        let generator = Truncated64IdGenerator::new(RandomIdGenerator::default());
+
+        for _ in 0..100 {
+            let id = generator.new_trace_id();
+            assert_eq!(high_bits(id), 0);
+            assert_ne!(id, TraceId::INVALID);
+        }
+    }
+
+    /// A generator returning trace IDs whose low 64 bits are all zero.
+    #[derive(Debug)]
+    struct HighBitsIdGenerator;
+
+    impl IdGenerator for HighBitsIdGenerator {
+        fn new_trace_id(&self) -> TraceId {
+            TraceId::from(1_u128 << 64)
+        }
+
+        fn new_span_id(&self) -> SpanId {
+            SpanId::from(1_u64)
+        }
+    }
+
+    #[test]
+    fn replaces_trace_ids_whose_low_bits_are_zero() {
+        let generator = Truncated64IdGenerator::new(HighBitsIdGenerator);
+
+        let id = generator.new_trace_id();
+
+        assert_eq!(high_bits(id), 0);
+        assert_ne!(id, TraceId::INVALID);
+        assert_eq!(generator.new_span_id(), SpanId::from(1_u64));
+    }
+
+    /// A generator returning the same trace and span IDs.
+    #[derive(Debug)]
+    struct FixedIdGenerator;
+
+    impl IdGenerator for FixedIdGenerator {
+        fn new_trace_id(&self) -> TraceId {
+            TraceId::from(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736_u128)
+        }
+
+        fn new_span_id(&self) -> SpanId {
+            SpanId::from(0x00f0_67aa_0ba9_02b7_u64)
+        }
+    }
+
+    #[test]
+    fn builds_providers_with_a_fixed_id_generator() {
+        let (builder, _) = crate::exporters::provider_builder(
+            FixedIdGenerator,
+            &AppConfigs::new(),
+            &OTLPConfigs::new(),
+            &TracesConfigs::default(),
+        );
+        let pipeline = test_utils::pipeline_with(builder.with_sampler(Sampler::AlwaysOn));
+
+        let ctx = helpers::ctx(&pipeline.tracer, SpanKind::Internal, "fixed");
+
+        assert_eq!(helpers::trace_id(&ctx), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(helpers::span_id(&ctx), "00f067aa0ba902b7");
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! In-memory exporter implementation.
+//!
+//! This module provides an exporter keeping finished spans in memory, so tests can assert
+//! the spans produced by application code: names, attributes, events and parent links.
+//! The provider is built like the production ones, except that every span is sampled.
+
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::{
+        provider_builder,
+        sampler::{self, SuppressingSampler},
+    },
+    processors, propagation,
+};
+use configs::{app::AppConfigs, otlp::OTLPConfigs};
+use opentelemetry::global;
+use opentelemetry_sdk::trace::{
+    IdGenerator, InMemorySpanExporter, RandomIdGenerator, Sampler, SdkTracerProvider,
+    SimpleSpanProcessor, SpanData,
+};
+use tracing::info;
+
+/// A handle reading the spans captured by the in-memory exporter.
+#[derive(Debug, Clone)]
+pub struct InMemorySpanExporterHandle {
+    exporter: InMemorySpanExporter,
+}
+
+impl InMemorySpanExporterHandle {
+    /// Returns the spans finished since installation or the last reset, in end order.
+    pub fn finished_spans(&self) -> Vec<SpanData> {
+        self.exporter.get_finished_spans().unwrap_or_default()
+    }
+
+    /// Discards the captured spans.
+    pub fn reset(&self) {
+        self.exporter.reset();
+    }
+}
+
+/// Installs the in-memory exporter for OpenTelemetry tracing.
+///
+/// The provider is registered globally and exports each span as soon as it ends, so
+/// the spans are available from the handle without flushing.
+///
+/// # Returns
+///
+/// * `Ok((SdkTracerProvider, InMemorySpanExporterHandle))` if installation is successful
+/// * `Err(TracesError)` if installation fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::{SpanKind, TraceContextExt};
+/// use traces::{exporters::in_memory, helpers, provider};
+///
+/// fn creates_a_server_span() {
+///     let (_provider, spans) = in_memory::install_in_memory().expect("Failed to install tracing");
+///
+///     let ctx = helpers::ctx(&provider::tracer(), SpanKind::Server, "handle");
+///     ctx.span().end();
+///
+///     let finished = spans.finished_spans();
+///     assert_eq!(finished.len(), 1);
+///     assert_eq!(finished[0].name, "handle");
+///     assert_eq!(finished[0].span_kind, SpanKind::Server);
+/// }
+/// ```
+pub fn install_in_memory() -> Result<(SdkTracerProvider, InMemorySpanExporterHandle), TracesError> {
+    install_in_memory_with_id_generator(RandomIdGenerator::default())
+}
+
+/// Installs the in-memory exporter using a custom trace and span ID generator.
+///
+/// Combined with `id_generator::SeededIdGenerator`, this lets tests assert exact
+/// trace and span IDs.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok((SdkTracerProvider, InMemorySpanExporterHandle))` if installation is successful
+/// * `Err(TracesError)` if installation fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use opentelemetry_sdk::trace::IdGenerator;
+/// use traces::{
+///     exporters::{id_generator::SeededIdGenerator, in_memory},
+///     helpers, provider,
+/// };
+///
+/// fn creates_a_deterministic_trace() {
+///     let expected = SeededIdGenerator::new(7).new_trace_id();
+///     let (_provider, _spans) =
+///         in_memory::install_in_memory_with_id_generator(SeededIdGenerator::new(7))
+///             .expect("Failed to install tracing");
+///
+///     let ctx = helpers::ctx(&provider::tracer(), SpanKind::Server, "handle");
+///     assert_eq!(helpers::trace_id(&ctx), expected.to_string());
+/// }
+/// ```
+pub fn install_in_memory_with_id_generator<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<(SdkTracerProvider, InMemorySpanExporterHandle), TracesError> {
+    let app_cfgs = AppConfigs::new();
+    let otlp_cfgs = OTLPConfigs::new();
+    let traces_cfgs = TracesConfigs::new();
+
+    let exporter = InMemorySpanExporter::default();
+
+    let (builder, ratio_sampler) =
+        provider_builder(id_generator, &app_cfgs, &otlp_cfgs, &traces_cfgs);
+    let builder = builder.with_sampler(SuppressingSampler::new(Sampler::AlwaysOn));
+
+    let builder = processors::with_exporting_processor(
+        builder,
+        SimpleSpanProcessor::new(exporter.clone()),
+        &traces_cfgs,
+    );
+
+    let provider = processors::with_processors(builder, &traces_cfgs).build();
+
+    global::set_tracer_provider(provider.clone());
+    sampler::register_ratio_sampler(ratio_sampler);
+    propagation::install(&traces_cfgs.propagators);
+
+    info!("traces::install in-memory tracer installed");
+
+    Ok((provider, InMemorySpanExporterHandle { exporter }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers, provider, test_utils};
+    use opentelemetry::{
+        KeyValue,
+        trace::{SpanKind, TraceContextExt},
+    };
+
+    #[test]
+    fn captures_the_spans_created_with_helpers() {
+        let _lock = test_utils::lock();
+        let (tracer_provider, spans) = install_in_memory().expect("install");
+
+        let parent = helpers::ctx(&provider::tracer(), SpanKind::Server, "handle");
+        let child = helpers::child(&provider::tracer(), &parent, SpanKind::Client, "query");
+        helpers::set_attribute(&child, "db.system", "postgresql");
+        child.span().end();
+        parent.span().end();
+
+        let finished = spans.finished_spans();
+        assert_eq!(finished.len(), 2);
+        assert_eq!(finished[0].name, "query");
+        assert_eq!(finished[0].span_kind, SpanKind::Client);
+        assert_eq!(
+            finished[0].parent_span_id,
+            parent.span().span_context().span_id()
+        );
+        assert!(
+            finished[0]
+                .attributes
+                .contains(&KeyValue::new("db.system", "postgresql"))
+        );
+        assert_eq!(finished[1].name, "handle");
+        assert_eq!(finished[1].span_kind, SpanKind::Server);
+
+        spans.reset();
+        assert!(spans.finished_spans().is_empty());
+
+        tracer_provider.shutdown().expect("shutdown");
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! JSON representation of spans.
+//!
+//! This module provides the conversion of finished spans into the JSON records written
+//! by the file exporter and the single-line stdout formats.
+
+use opentelemetry::{KeyValue, Value, trace::Status};
+use opentelemetry_sdk::{Resource, trace::SpanData};
+use serde_json::{Map, Value as Json, json};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Converts a span into its JSON representation.
+///
+/// # Arguments
+///
+/// * `span` - The finished span
+/// * `resource` - The resource attributes added to the record, or `None` to leave them out
+///
+/// # Returns
+///
+/// A JSON object describing the span
+pub(crate) fn span_to_json(span: &SpanData, resource: Option<&Map<String, Json>>) -> Json {
+    let (status, status_message) = match &span.status {
+        Status::Unset => ("unset", String::new()),
+        Status::Ok => ("ok", String::new()),
+        Status::Error { description } => ("error", description.to_string()),
+    };
+
+    let events: Vec<Json> = span
+        .events
+        .events
+        .iter()
+        .map(|event| {
+            json!({
+                "name": event.name.to_string(),
+                "time_unix_nano": unix_nanos(event.timestamp),
+                "attributes": attributes(&event.attributes),
+            })
+        })
+        .collect();
+
+    let mut record = json!({
+        "trace_id": span.span_context.trace_id().to_string(),
+        "span_id": span.span_context.span_id().to_string(),
+        "parent_span_id": span.parent_span_id.to_string(),
+        "name": span.name.to_string(),
+        "kind": format!("{:?}", span.span_kind),
+        "start_time_unix_nano": unix_nanos(span.start_time),
+        "end_time_unix_nano": unix_nanos(span.end_time),
+        "status": status,
+        "status_message": status_message,
+        "attributes": attributes(&span.attributes),
+        "events": events,
+    });
+
+    if let Some(resource) = resource {
+        record["resource"] = Json::Object(resource.clone());
+    }
+
+    record
+}
+
+/// Converts the resource attributes into a JSON object.
+pub(crate) fn resource_to_json(resource: &Resource) -> Map<String, Json> {
+    resource
+        .iter()
+        .map(|(key, value)| (key.to_string(), to_json_value(value)))
+        .collect()
+}
+
+/// Converts a timestamp into nanoseconds since the Unix epoch.
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default()
+}
+
+/// Converts attributes into a JSON object.
+fn attributes(attributes: &[KeyValue]) -> Map<String, Json> {
+    attributes
+        .iter()
+        .map(|kv| (kv.key.to_string(), to_json_value(&kv.value)))
+        .collect()
+}
+
+/// Converts an attribute value into a JSON value.
+fn to_json_value(value: &Value) -> Json {
+    match value {
+        Value::Bool(value) => Json::Bool(*value),
+        Value::I64(value) => json!(value),
+        Value::F64(value) => json!(value),
+        value => Json::String(value.as_str().into_owned()),
+    }
+}
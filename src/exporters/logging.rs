@@ -0,0 +1,290 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Export failure logging.
+//!
+//! This module provides a span exporter wrapper logging export failures and recoveries, so
+//! operators see a collector outage in the service logs without an external metrics system.
+
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{SpanData, SpanExporter},
+};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{error, info};
+
+/// The failures since the last successful export.
+#[derive(Debug, Default)]
+struct FailureState {
+    failures: u64,
+    suppressed: u64,
+    last_logged: Option<Instant>,
+}
+
+/// A span exporter wrapper logging export failures with a rate limit.
+///
+/// A failed export is logged as an error with the running count of consecutive failures,
+/// at most once per interval: the failures in between are only counted, and reported as
+/// `suppressed` by the next log, so an outage does not flood the logs. The first successful
+/// export after failures is logged once, with the number of failed exports.
+#[derive(Debug)]
+pub struct LoggingExporter<E> {
+    inner: E,
+    interval: Duration,
+    state: Mutex<FailureState>,
+}
+
+impl<E: SpanExporter> LoggingExporter<E> {
+    /// Creates a new `LoggingExporter` wrapping the provided exporter.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The exporter sending the spans
+    /// * `interval` - The minimum time between two failure logs
+    ///
+    /// # Returns
+    ///
+    /// A new `LoggingExporter` instance
+    pub fn new(inner: E, interval: Duration) -> LoggingExporter<E> {
+        LoggingExporter {
+            inner,
+            interval,
+            state: Mutex::new(FailureState::default()),
+        }
+    }
+
+    /// Records the result of an export, logging a failure or a recovery if due.
+    fn record(&self, result: &OTelSdkResult, spans: usize) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+
+        match result {
+            Ok(()) if state.failures > 0 => {
+                info!(
+                    failed_exports = state.failures,
+                    "traces::export span export recovered"
+                );
+                *state = FailureState::default();
+            }
+            Ok(()) => {}
+            Err(err) => {
+                state.failures += 1;
+
+                let now = Instant::now();
+                let is_due = state
+                    .last_logged
+                    .is_none_or(|last| now.duration_since(last) >= self.interval);
+
+                if is_due {
+                    error!(
+                        error = err.to_string(),
+                        spans = spans,
+                        consecutive_failures = state.failures,
+                        suppressed = state.suppressed,
+                        "traces::export failure to export spans"
+                    );
+                    state.last_logged = Some(now);
+                    state.suppressed = 0;
+                } else {
+                    state.suppressed += 1;
+                }
+            }
+        }
+    }
+}
+
+impl<E: SpanExporter> SpanExporter for LoggingExporter<E> {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let spans = batch.len();
+        let result = self.inner.export(batch).await;
+        self.record(&result, spans);
+
+        result
+    }
+
+    fn shutdown_with_timeout(&mut self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use opentelemetry::trace::Tracer;
+    use opentelemetry_sdk::error::OTelSdkError;
+    use std::{
+        collections::HashMap,
+        fmt,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        thread,
+    };
+    use tracing::{
+        Event, Level, Subscriber,
+        field::{Field, Visit},
+    };
+    use tracing_subscriber::{
+        layer::{Context, Layer, SubscriberExt},
+        registry,
+    };
+
+    /// An exporter failing its first `failures` exports.
+    #[derive(Debug)]
+    struct FlakyExporter {
+        failures: AtomicUsize,
+    }
+
+    impl SpanExporter for FlakyExporter {
+        async fn export(&self, _batch: Vec<SpanData>) -> OTelSdkResult {
+            let failing = self
+                .failures
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                    left.checked_sub(1)
+                })
+                .is_ok();
+
+            if failing {
+                Err(OTelSdkError::InternalFailure(
+                    "collector unavailable".into(),
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    /// A log event: its level and fields, formatted.
+    #[derive(Debug)]
+    struct Log {
+        level: Level,
+        fields: HashMap<String, String>,
+    }
+
+    impl Visit for Log {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.fields
+                .insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    /// A layer capturing the log events.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<Log>>>);
+
+    impl<S: Subscriber> Layer<S> for Capture {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut log = Log {
+                level: *event.metadata().level(),
+                fields: HashMap::new(),
+            };
+            event.record(&mut log);
+            self.0.lock().unwrap().push(log);
+        }
+    }
+
+    /// Exports one batch per outcome through a `LoggingExporter` and returns the logs.
+    ///
+    /// The exporter fails `failures` times, then succeeds. `pause` runs before each export.
+    fn export(
+        failures: usize,
+        exports: usize,
+        interval: Duration,
+        pause: impl Fn(usize),
+    ) -> Vec<Log> {
+        let pipeline = test_utils::pipeline();
+        pipeline.tracer.in_span("span", |_| {});
+        let batch = pipeline.spans();
+
+        let exporter = LoggingExporter::new(
+            FlakyExporter {
+                failures: AtomicUsize::new(failures),
+            },
+            interval,
+        );
+        let capture = Capture::default();
+        let subscriber = registry().with(capture.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            for export in 0..exports {
+                pause(export);
+                let _ = test_utils::block_on(exporter.export(batch.clone()));
+            }
+        });
+
+        std::mem::take(&mut *capture.0.lock().unwrap())
+    }
+
+    #[test]
+    fn logs_the_first_failure_and_the_recovery() {
+        let logs = export(3, 5, Duration::from_secs(3600), |_| {});
+
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].level, Level::ERROR);
+        assert_eq!(logs[0].fields["consecutive_failures"], "1");
+        assert_eq!(logs[0].fields["suppressed"], "0");
+        assert_eq!(logs[0].fields["spans"], "1");
+        assert!(logs[0].fields["error"].contains("collector unavailable"));
+        assert_eq!(logs[1].level, Level::INFO);
+        assert_eq!(logs[1].fields["failed_exports"], "3");
+    }
+
+    #[test]
+    fn reports_the_suppressed_failures_once_the_interval_elapsed() {
+        let interval = Duration::from_millis(50);
+        let logs = export(4, 4, interval, |export| {
+            if export == 3 {
+                thread::sleep(interval * 2);
+            }
+        });
+
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].fields["consecutive_failures"], "1");
+        assert_eq!(logs[1].level, Level::ERROR);
+        assert_eq!(logs[1].fields["consecutive_failures"], "4");
+        assert_eq!(logs[1].fields["suppressed"], "2");
+    }
+
+    #[test]
+    fn logs_a_new_outage_after_a_recovery() {
+        let exporter = LoggingExporter::new(
+            FlakyExporter {
+                failures: AtomicUsize::new(0),
+            },
+            Duration::from_secs(3600),
+        );
+        let failure = Err(OTelSdkError::InternalFailure(
+            "collector unavailable".into(),
+        ));
+
+        exporter.record(&failure, 1);
+        exporter.record(&Ok(()), 1);
+        exporter.record(&failure, 1);
+
+        let state = exporter.state.lock().unwrap();
+        assert_eq!(state.failures, 1);
+        assert_eq!(state.suppressed, 0);
+        assert!(state.last_logged.is_some());
+    }
+}
@@ -9,13 +9,362 @@
 //! feature flags.
 //!
 
-#[cfg(any(feature = "stdout", feature = "otlp"))]
-mod sampler;
+use crate::config::TracesConfigs;
+use crate::errors::TracesError;
+use configs::{app::AppConfigs, otlp::OTLPConfigs};
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::{
+    Resource,
+    trace::{IdGenerator, TracerProviderBuilder},
+};
+use std::str::FromStr;
+
+pub mod sampler;
+
+pub mod id_generator;
 
 #[cfg(feature = "otlp")]
 pub mod otlp_grpc;
 
+#[cfg(feature = "otlp-http")]
+pub mod otlp_http;
+
 #[cfg(feature = "stdout")]
 pub mod stdout;
 
+#[cfg(feature = "file")]
+pub mod file;
+
+#[cfg(feature = "testing")]
+pub mod in_memory;
+
+#[cfg(any(feature = "stdout", feature = "file"))]
+mod json;
+
+#[cfg(any(feature = "otlp", feature = "otlp-http", feature = "file"))]
+pub mod logging;
+
 pub mod noop;
+
+/// Compression applied to OTLP export requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceCompression {
+    /// Requests are sent uncompressed.
+    None,
+    /// Requests are compressed with gzip.
+    #[default]
+    Gzip,
+    /// Requests are compressed with zstd.
+    Zstd,
+}
+
+impl FromStr for TraceCompression {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(TraceCompression::None),
+            "gzip" => Ok(TraceCompression::Gzip),
+            "zstd" => Ok(TraceCompression::Zstd),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+#[cfg(feature = "otlp")]
+impl TraceCompression {
+    /// Converts the setting into the OTLP exporter compression, if any.
+    pub(crate) fn to_otlp(self) -> Option<opentelemetry_otlp::Compression> {
+        match self {
+            TraceCompression::None => None,
+            TraceCompression::Gzip => Some(opentelemetry_otlp::Compression::Gzip),
+            TraceCompression::Zstd => Some(opentelemetry_otlp::Compression::Zstd),
+        }
+    }
+}
+
+/// The exporter installed by `provider::install`, selected at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExporterKind {
+    /// The exporter selected by the enabled features: OTLP gRPC, then OTLP HTTP, then stdout,
+    /// then the no-op tracer.
+    #[default]
+    Auto,
+    /// OTLP gRPC, or OTLP HTTP when only the `otlp-http` feature is enabled.
+    Otlp,
+    /// The stdout exporter, requires the `stdout` feature.
+    Stdout,
+    /// The no-op tracer, whatever the enabled features.
+    Noop,
+}
+
+impl FromStr for ExporterKind {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(ExporterKind::Auto),
+            "otlp" => Ok(ExporterKind::Otlp),
+            "stdout" => Ok(ExporterKind::Stdout),
+            "noop" => Ok(ExporterKind::Noop),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// Output format of the stdout exporter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StdoutFormat {
+    /// Spans are pretty-printed over several lines.
+    #[default]
+    Pretty,
+    /// Spans are written as one JSON object per line, including the resource.
+    Json,
+    /// Spans are written as one JSON object per line, without the resource.
+    Compact,
+}
+
+impl FromStr for StdoutFormat {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pretty" => Ok(StdoutFormat::Pretty),
+            "json" => Ok(StdoutFormat::Json),
+            "compact" => Ok(StdoutFormat::Compact),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// Creates the tracer provider builder shared by all exporters.
+///
+/// The builder is configured with the sampler, the validated id generator, the span limits
+/// and the resource; each exporter then only registers its exporting processor. The id
+/// generator is truncated to 64-bit trace IDs when `TracesConfigs::trace_id_bits` is `64`.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+/// * `app_cfgs` - Application configuration describing the service
+/// * `otlp_cfgs` - OpenTelemetry configuration containing sampling rate settings
+/// * `traces_cfgs` - Tracing configuration, including the span limits and trace ID bits
+///
+/// # Returns
+///
+/// The configured tracer provider builder, and the ratio sampler of the provider to register
+/// with `sampler::register_ratio_sampler` when the provider is installed
+pub(crate) fn provider_builder<G: IdGenerator + 'static>(
+    id_generator: G,
+    app_cfgs: &AppConfigs,
+    otlp_cfgs: &OTLPConfigs,
+    traces_cfgs: &TracesConfigs,
+) -> (TracerProviderBuilder, sampler::ReloadableRatioSampler) {
+    let id_generator = id_generator::ValidatedIdGenerator::new(id_generator);
+    let builder = match traces_cfgs.trace_id_bits {
+        id_generator::TraceIdBits::Bits64 => TracerProviderBuilder::default()
+            .with_id_generator(id_generator::Truncated64IdGenerator::new(id_generator)),
+        id_generator::TraceIdBits::Bits128 => {
+            TracerProviderBuilder::default().with_id_generator(id_generator)
+        }
+    };
+
+    let (sampler, ratio_sampler) = sampler::get_sampler(app_cfgs, otlp_cfgs, traces_cfgs);
+
+    let builder = builder
+        .with_sampler(sampler)
+        .with_max_events_per_span(traces_cfgs.max_events_per_span)
+        .with_max_attributes_per_span(traces_cfgs.max_attributes_per_span)
+        .with_max_links_per_span(traces_cfgs.max_links_per_span)
+        .with_max_attributes_per_event(traces_cfgs.max_attributes_per_event)
+        .with_resource(build_resource(app_cfgs, traces_cfgs));
+
+    (builder, ratio_sampler)
+}
+
+/// Builds the resource describing the service, shared by all exporters.
+///
+/// Attributes are merged with increasing precedence: `OTEL_RESOURCE_ATTRIBUTES`, then
+/// `TracesConfigs::resource_attributes`, then the service attributes derived from
+/// `AppConfigs` (`service.name`, `service.namespace`, `environment`) and `library.language`.
+/// `service.name` is overridden by `TracesConfigs::service_name` when set.
+///
+/// # Arguments
+///
+/// * `app_cfgs` - Application configuration describing the service
+/// * `traces_cfgs` - Tracing configuration holding the extra resource attributes
+///
+/// # Returns
+///
+/// The resource attached to every exported span
+fn build_resource(app_cfgs: &AppConfigs, traces_cfgs: &TracesConfigs) -> Resource {
+    // The builder detects `OTEL_RESOURCE_ATTRIBUTES`, later attributes take precedence
+    Resource::builder()
+        .with_attributes(
+            traces_cfgs
+                .resource_attributes
+                .iter()
+                .map(|(key, value)| KeyValue::new(key.clone(), value.clone())),
+        )
+        .with_service_name(traces_cfgs.service_name(app_cfgs))
+        .with_attribute(KeyValue::new(
+            "service.namespace",
+            format!("{}", app_cfgs.namespace),
+        ))
+        .with_attribute(KeyValue::new("environment", format!("{}", app_cfgs.env)))
+        .with_attribute(KeyValue::new("library.language", "rust"))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use opentelemetry::{
+        Key, Value,
+        trace::{TraceContextExt, Tracer},
+    };
+    use opentelemetry_sdk::trace::{RandomIdGenerator, Sampler};
+    use std::collections::HashMap;
+
+    #[test]
+    fn parses_each_compression() {
+        assert_eq!("none".parse(), Ok(TraceCompression::None));
+        assert_eq!("gzip".parse(), Ok(TraceCompression::Gzip));
+        assert_eq!(" ZSTD ".parse(), Ok(TraceCompression::Zstd));
+    }
+
+    #[test]
+    fn rejects_an_unknown_compression() {
+        assert_eq!(
+            "brotli".parse::<TraceCompression>(),
+            Err(TracesError::ConversionError)
+        );
+    }
+
+    #[test]
+    fn ignores_an_unknown_configured_compression() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_OTLP_COMPRESSION", "brotli")]);
+
+        assert_eq!(TracesConfigs::new().compression, None);
+    }
+
+    #[cfg(feature = "otlp")]
+    #[test]
+    fn maps_to_the_otlp_compression() {
+        assert!(TraceCompression::None.to_otlp().is_none());
+        assert!(matches!(
+            TraceCompression::Gzip.to_otlp(),
+            Some(opentelemetry_otlp::Compression::Gzip)
+        ));
+        assert!(matches!(
+            TraceCompression::Zstd.to_otlp(),
+            Some(opentelemetry_otlp::Compression::Zstd)
+        ));
+    }
+
+    #[test]
+    fn drops_the_attributes_and_events_beyond_the_limits() {
+        let traces_cfgs = TracesConfigs {
+            max_attributes_per_span: 2,
+            max_events_per_span: 1,
+            max_attributes_per_event: 1,
+            ..TracesConfigs::default()
+        };
+        let (builder, _) = provider_builder(
+            RandomIdGenerator::default(),
+            &AppConfigs::new(),
+            &OTLPConfigs::new(),
+            &traces_cfgs,
+        );
+        let builder = builder.with_sampler(Sampler::AlwaysOn);
+        let pipeline = test_utils::pipeline_with(builder);
+
+        pipeline.tracer.in_span("limited", |cx| {
+            let span = cx.span();
+            for index in 0..4_i64 {
+                span.set_attribute(KeyValue::new(format!("attribute.{index}"), index));
+            }
+            span.add_event(
+                "first",
+                vec![KeyValue::new("a", 1_i64), KeyValue::new("b", 2_i64)],
+            );
+            span.add_event("second", vec![]);
+        });
+
+        let span = pipeline.spans().pop().expect("exported span");
+        assert_eq!(span.attributes.len(), 2);
+        assert_eq!(span.dropped_attributes_count, 2);
+        assert_eq!(span.events.events.len(), 1);
+        assert_eq!(span.events.dropped_count, 1);
+        assert_eq!(span.events.events[0].attributes.len(), 1);
+        assert_eq!(span.events.events[0].dropped_attributes_count, 1);
+    }
+
+    #[test]
+    fn truncates_the_trace_ids_to_64_bits_when_configured() {
+        let traces_cfgs = TracesConfigs {
+            trace_id_bits: id_generator::TraceIdBits::Bits64,
+            ..TracesConfigs::default()
+        };
+        let (builder, _) = provider_builder(
+            RandomIdGenerator::default(),
+            &AppConfigs::new(),
+            &OTLPConfigs::new(),
+            &traces_cfgs,
+        );
+        let pipeline = test_utils::pipeline_with(builder.with_sampler(Sampler::AlwaysOn));
+
+        for _ in 0..10 {
+            pipeline.tracer.in_span("truncated", |_| {});
+        }
+
+        let spans = pipeline.spans();
+        assert_eq!(spans.len(), 10);
+        for span in spans {
+            let id = u128::from_be_bytes(span.span_context.trace_id().to_bytes());
+            assert_eq!(id >> 64, 0);
+            assert_ne!(id, 0);
+        }
+    }
+
+    /// Builds a provider the way the exporters do and returns the resource it exports with.
+    fn exported_resource<G: IdGenerator + 'static>(
+        id_generator: G,
+        traces_cfgs: &TracesConfigs,
+    ) -> Resource {
+        let (builder, _) = provider_builder(
+            id_generator,
+            &AppConfigs::new(),
+            &OTLPConfigs::new(),
+            traces_cfgs,
+        );
+
+        test_utils::exported_resource(builder)
+    }
+
+    #[test]
+    fn exporters_share_an_identical_resource() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[(
+            "OTEL_RESOURCE_ATTRIBUTES",
+            "deployment.region=eu,team=platform",
+        )]);
+        let traces_cfgs = TracesConfigs {
+            service_name: Some("checkout".to_string()),
+            resource_attributes: HashMap::from([("team".to_string(), "payments".to_string())]),
+            ..TracesConfigs::default()
+        };
+
+        let first = exported_resource(RandomIdGenerator::default(), &traces_cfgs);
+        let second = exported_resource(RandomIdGenerator::default(), &traces_cfgs);
+
+        assert_eq!(first, second);
+        let value = |key: &'static str| first.get(&Key::from_static_str(key));
+        assert_eq!(value("service.name"), Some(Value::from("checkout")));
+        assert_eq!(value("team"), Some(Value::from("payments")));
+        assert_eq!(value("deployment.region"), Some(Value::from("eu")));
+        assert_eq!(value("library.language"), Some(Value::from("rust")));
+    }
+}
@@ -7,9 +7,23 @@
 //! This module provides a no-op (no operation) tracer implementation that can be used
 //! when tracing is disabled or when no specific exporter features are enabled.
 //! It fulfills the tracer interface without performing any actual tracing operations.
+//! The configured propagators are still registered, so trace context keeps flowing
+//! through this process between services that do export spans.
 
-use crate::errors::TracesError;
-use opentelemetry_sdk::trace::SdkTracerProvider;
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::{
+        provider_builder,
+        sampler::{self, ReloadableRatioSampler},
+    },
+    propagation,
+};
+use configs::{app::AppConfigs, otlp::OTLPConfigs};
+use opentelemetry::global;
+use opentelemetry_sdk::trace::{
+    IdGenerator, RandomIdGenerator, Sampler, SdkTracerProvider, TracerProviderBuilder,
+};
 use tracing::info;
 
 /// Installs a no-op tracer provider that doesn't export any telemetry data.
@@ -20,7 +34,8 @@ use tracing::info;
 ///
 /// # Returns
 ///
-/// * `Ok(SdkTracerProvider)` - A default tracer provider that doesn't export data
+/// * `Ok(SdkTracerProvider)` - A tracer provider carrying the service resource that doesn't
+///   export data
 /// * `Err(TracesError)` - If installation fails (unlikely with no-op implementation)
 ///
 /// # Examples
@@ -34,7 +49,143 @@ use tracing::info;
 /// }
 /// ```
 pub fn install() -> Result<SdkTracerProvider, TracesError> {
+    install_with_id_generator(RandomIdGenerator::default())
+}
+
+/// Installs a no-op tracer provider using a custom trace and span ID generator.
+///
+/// No telemetry is exported and no root span is sampled, but the provider is built like the
+/// exporting ones, so it carries the service resource, span limits and the validated
+/// identifiers of the provided generator. Spans continuing a sampled remote trace stay
+/// sampled, so the context injected downstream keeps the sampled flag and the services
+/// exporting spans keep the rest of the trace. The provider is registered globally,
+/// replacing any previously installed one.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` - A tracer provider that doesn't export data
+/// * `Err(TracesError)` - If installation fails (unlikely with no-op implementation)
+pub fn install_with_id_generator<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
+    let traces_cfgs = TracesConfigs::new();
+
+    let (builder, ratio_sampler) = builder(id_generator, &traces_cfgs);
+    let provider = builder.build();
+
+    global::set_tracer_provider(provider.clone());
+    sampler::register_ratio_sampler(ratio_sampler);
+    propagation::install(&traces_cfgs.propagators);
+
     info!("traces::install noop tracer installed");
 
-    Ok(SdkTracerProvider::default())
+    Ok(provider)
+}
+
+/// Returns the builder of the no-op tracer provider, without any exporter, and its ratio
+/// sampler, left unused so `provider::set_sampling_ratio` has no effect.
+fn builder<G: IdGenerator + 'static>(
+    id_generator: G,
+    traces_cfgs: &TracesConfigs,
+) -> (TracerProviderBuilder, ReloadableRatioSampler) {
+    let (builder, ratio_sampler) = provider_builder(
+        id_generator,
+        &AppConfigs::new(),
+        &OTLPConfigs::new(),
+        traces_cfgs,
+    );
+
+    // Without an exporter nothing would be exported anyway, sampling no root span saves the
+    // work, while following the parent keeps the sampled flag of the propagated context
+    let builder = builder.with_sampler(Sampler::ParentBased(Box::new(Sampler::AlwaysOff)));
+
+    (builder, ratio_sampler)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers, injectors, test_utils};
+    use opentelemetry::{
+        Key, Value,
+        propagation::TextMapCompositePropagator,
+        trace::{
+            SpanContext, SpanId, SpanKind, TraceContextExt, TraceFlags, TraceId, TraceState,
+            TracerProvider,
+        },
+    };
+    use std::collections::HashMap;
+
+    /// Returns a context continuing a remote trace with the provided trace flags.
+    fn remote_parent(flags: TraceFlags) -> opentelemetry::Context {
+        opentelemetry::Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from(0x4bf92f3577b34da6a3ce929d0e0e4736_u128),
+            SpanId::from(0x00f067aa0ba902b7_u64),
+            flags,
+            true,
+            TraceState::default(),
+        ))
+    }
+
+    #[test]
+    fn carries_the_configured_service_name() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_SERVICE_NAME", "checkout")]);
+        let traces_cfgs = TracesConfigs::new();
+
+        let (builder, _) = builder(RandomIdGenerator::default(), &traces_cfgs);
+        let resource = test_utils::exported_resource(builder);
+
+        assert_eq!(
+            resource.get(&Key::from_static_str("service.name")),
+            Some(Value::from("checkout"))
+        );
+    }
+
+    #[test]
+    fn samples_no_root_span() {
+        let (builder, _) = builder(RandomIdGenerator::default(), &TracesConfigs::default());
+        let provider = builder.build();
+        let tracer = global::BoxedTracer::new(Box::new(provider.tracer("noop")));
+
+        let ctx = helpers::ctx(&tracer, SpanKind::Server, "root");
+
+        assert!(!ctx.span().span_context().is_sampled());
+    }
+
+    #[test]
+    fn keeps_the_sampled_flag_of_the_remote_parent() {
+        let (builder, _) = builder(RandomIdGenerator::default(), &TracesConfigs::default());
+        let provider = builder.build();
+        let tracer = global::BoxedTracer::new(Box::new(provider.tracer("noop")));
+
+        for flags in [TraceFlags::SAMPLED, TraceFlags::default()] {
+            let parent = remote_parent(flags);
+            let ctx = helpers::child(&tracer, &parent, SpanKind::Server, "continued");
+
+            assert_eq!(ctx.span().span_context().trace_flags(), flags);
+        }
+    }
+
+    #[test]
+    fn registers_the_propagators() {
+        let _lock = test_utils::lock();
+        global::set_text_map_propagator(TextMapCompositePropagator::new(Vec::new()));
+
+        install().expect("noop provider installed");
+        let tracer = global::BoxedTracer::new(Box::new(global::tracer("noop")));
+        let parent = remote_parent(TraceFlags::SAMPLED);
+        let ctx = helpers::child(&tracer, &parent, SpanKind::Server, "continued");
+
+        let mut headers = HashMap::new();
+        injectors::inject(&ctx, &mut headers);
+
+        let traceparent = headers.get("traceparent").expect("traceparent injected");
+        assert!(traceparent.contains("4bf92f3577b34da6a3ce929d0e0e4736"));
+        assert!(traceparent.ends_with("-01"));
+    }
 }
@@ -8,25 +8,45 @@
 //! over gRPC. This exporter is suitable for production environments where traces need to be
 //! sent to an OpenTelemetry collector or compatible backend.
 
-use crate::{errors::TracesError, exporters::sampler::get_sampler};
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::{
+        TraceCompression,
+        id_generator::{self, SharedIdGenerator},
+        provider_builder,
+        sampler::{self, ReloadableRatioSampler, SuppressingSampler},
+    },
+    processors::{self, SharedProcessor},
+    propagation,
+};
 use configs::{app::AppConfigs, otlp::OTLPConfigs};
-use opentelemetry::{KeyValue, global, propagation::TextMapCompositePropagator};
-use opentelemetry_otlp::{Compression, Protocol, SpanExporter, WithExportConfig, WithTonicConfig};
-use opentelemetry_sdk::{
-    propagation::{BaggagePropagator, TraceContextPropagator},
-    resource::Resource,
-    trace::{RandomIdGenerator, SdkTracerProvider, TracerProviderBuilder},
+use opentelemetry::global;
+use opentelemetry_otlp::{
+    Protocol, SpanExporter, SpanExporterBuilder, TonicExporterBuilderSet, WithExportConfig,
+    WithTonicConfig,
+};
+use opentelemetry_sdk::trace::{IdGenerator, Sampler, SdkTracerProvider, SpanProcessor};
+use std::{
+    collections::HashMap,
+    fs,
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    sync::Arc,
+    thread,
+    time::Duration,
 };
-use tracing::{error, info};
+use tonic::{
+    metadata::{MetadataKey, MetadataMap, MetadataValue},
+    service::Interceptor,
+    transport::{Certificate, Channel, ClientTlsConfig, Identity},
+};
+use tracing::{error, info, warn};
 
 /// Installs the OTLP gRPC exporter for OpenTelemetry tracing.
 ///
 /// This function configures and installs a gRPC-based exporter that sends trace data
-/// to an OpenTelemetry collector or compatible backend.
-///
-/// # Arguments
-///
-/// * `cfg` - Application configuration containing trace settings
+/// to an OpenTelemetry collector or compatible backend. Trace and span IDs come from the
+/// generator selected by `TRACES_ID_GENERATOR`.
 ///
 /// # Returns
 ///
@@ -43,53 +63,1035 @@ use tracing::{error, info};
 /// }
 /// ```
 pub fn install() -> Result<SdkTracerProvider, TracesError> {
-    let app_cfgs = AppConfigs::new();
+    install_with_id_generator(id_generator::configured(TracesConfigs::new().id_generator))
+}
+
+/// Installs the OTLP gRPC exporter using a custom trace and span ID generator.
+///
+/// The generator is wrapped in a `ValidatedIdGenerator` so invalid identifiers
+/// are never emitted. See `exporters::id_generator` for guidance on implementing one.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError)` if installation fails
+pub fn install_with_id_generator<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
+    let builder = Builder::new();
+    let (provider, ratio_sampler) =
+        builder.build_with(id_generator, |exporter_builder| exporter_builder)?;
+
+    Ok(builder.register(provider, ratio_sampler))
+}
+
+/// Installs the OTLP gRPC exporter with a custom interceptor on the export channel.
+///
+/// The interceptor is applied to every export request, e.g. to sign requests for
+/// collectors behind bespoke authentication. Trace and span IDs come from the generator
+/// selected by `TRACES_ID_GENERATOR`.
+///
+/// # Arguments
+///
+/// * `interceptor` - The tonic interceptor applied to export requests
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError)` if installation fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use tonic::{Request, Status, metadata::MetadataValue};
+/// use traces::exporters::otlp_grpc;
+///
+/// fn sign(mut request: Request<()>) -> Result<Request<()>, Status> {
+///     request
+///         .metadata_mut()
+///         .insert("x-signature", MetadataValue::from_static("signature"));
+///     Ok(request)
+/// }
+///
+/// fn main() {
+///     let provider = otlp_grpc::install_with_interceptor(sign).expect("Failed to install OTLP exporter");
+/// }
+/// ```
+pub fn install_with_interceptor<I>(interceptor: I) -> Result<SdkTracerProvider, TracesError>
+where
+    I: Interceptor + Clone + Send + Sync + 'static,
+{
+    let builder = Builder::new();
+    let id_generator = id_generator::configured(builder.traces_cfgs.id_generator);
+    let (provider, ratio_sampler) = builder.build_with(id_generator, |exporter_builder| {
+        exporter_builder.with_interceptor(interceptor.clone())
+    })?;
+
+    Ok(builder.register(provider, ratio_sampler))
+}
+
+/// Checks that the configured collector accepts connections.
+///
+/// A TCP connection is opened to the host and port of the OTLP endpoint, within the
+/// configured connect timeout, and closed right away. No span is exported, so the check
+/// is cheap enough for a readiness probe. With the default `TracesConfigs::connect_retries`
+/// of 0, `install` does not probe the collector, so an unreachable collector never blocks
+/// the service boot. With retries, `install` runs the same probe before building the
+/// exporter and retries it with backoff, blocking the boot until the collector accepts
+/// connections or the retries are exhausted.
+///
+/// # Returns
+///
+/// * `Ok(())` if a connection to the collector was established
+/// * `Err(TracesError::InvalidConfigError)` if the endpoint is invalid
+/// * `Err(TracesError::CollectorUnreachableError)` if no connection could be established
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::exporters::otlp_grpc;
+///
+/// fn readyz() -> u16 {
+///     match otlp_grpc::check_connectivity() {
+///         Ok(()) => 200,
+///         Err(_) => 503,
+///     }
+/// }
+/// ```
+pub fn check_connectivity() -> Result<(), TracesError> {
     let otlp_cfgs = OTLPConfigs::new();
+    let traces_cfgs = TracesConfigs::new();
+
+    let timeout = traces_cfgs
+        .connect_timeout
+        .unwrap_or(otlp_cfgs.exporter_timeout);
+
+    connect(&otlp_cfgs.endpoint, timeout)
+}
 
-    let exporter = match SpanExporter::builder()
-        .with_tonic()
-        .with_protocol(Protocol::Grpc)
-        .with_timeout(otlp_cfgs.exporter_timeout)
-        .with_endpoint(&otlp_cfgs.endpoint)
-        .with_compression(Compression::Gzip)
-        .build()
+/// Opens and closes a TCP connection to the host and port of an endpoint.
+fn connect(endpoint: &str, timeout: Duration) -> Result<(), TracesError> {
+    validate_endpoint(endpoint)?;
+
+    let uri = endpoint
+        .trim()
+        .parse::<http::Uri>()
+        .map_err(|err| TracesError::InvalidConfigError(err.to_string()))?;
+    let host = uri.host().unwrap_or_default();
+    let port = uri.port_u16().unwrap_or(match uri.scheme_str() {
+        Some("https") => 443,
+        _ => 80,
+    });
+
+    let unreachable = |err: std::io::Error| {
+        warn!(
+            error = err.to_string(),
+            host = host,
+            port = port,
+            "OTLP collector is unreachable"
+        );
+        TracesError::CollectorUnreachableError(format!("{host}:{port}: {err}"))
+    };
+
+    let addrs = (host.trim_matches(['[', ']']), port)
+        .to_socket_addrs()
+        .map_err(unreachable)?;
+
+    let mut last_err = std::io::Error::other("the host resolved to no address");
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(_) => return Ok(()),
+            Err(err) => last_err = err,
+        }
+    }
+
+    Err(unreachable(last_err))
+}
+
+/// A builder configuring the OTLP gRPC tracer provider programmatically.
+///
+/// The builder starts from the environment configuration used by `install`, and each
+/// method overrides one setting. Unlike `install`, `build` does not register the provider
+/// globally, which lets libraries embedding this crate own their provider.
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry_sdk::trace::Sampler;
+/// use std::{collections::HashMap, time::Duration};
+/// use traces::exporters::{TraceCompression, otlp_grpc::Builder};
+///
+/// fn main() {
+///     let provider = Builder::new()
+///         .endpoint("http://collector:4317")
+///         .timeout(Duration::from_secs(5))
+///         .compression(TraceCompression::Zstd)
+///         .headers(HashMap::from([("x-tenant".to_owned(), "acme".to_owned())]))
+///         .sampler(Sampler::AlwaysOn)
+///         .build()
+///         .expect("Failed to build OTLP tracer provider");
+/// }
+/// ```
+pub struct Builder {
+    app_cfgs: AppConfigs,
+    otlp_cfgs: OTLPConfigs,
+    traces_cfgs: TracesConfigs,
+    tls: Option<ClientTlsConfig>,
+    sampler: Option<Sampler>,
+    id_generator: Option<SharedIdGenerator>,
+    processors: Vec<SharedProcessor>,
+}
+
+impl Builder {
+    /// Creates a new `Builder` from the environment configuration.
+    ///
+    /// # Returns
+    ///
+    /// A new `Builder` instance
+    pub fn new() -> Builder {
+        Builder {
+            app_cfgs: AppConfigs::new(),
+            otlp_cfgs: OTLPConfigs::new(),
+            traces_cfgs: TracesConfigs::new(),
+            tls: None,
+            sampler: None,
+            id_generator: None,
+            processors: Vec::new(),
+        }
+    }
+
+    /// Sets the collector endpoint, e.g. `http://localhost:4317`.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Builder {
+        self.otlp_cfgs.endpoint = endpoint.into();
+        self
+    }
+
+    /// Sets the timeout of each export request.
+    pub fn timeout(mut self, timeout: Duration) -> Builder {
+        self.traces_cfgs.export_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout of the connection establishment of the export channel.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Builder {
+        self.traces_cfgs.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the compression of the export requests.
+    pub fn compression(mut self, compression: TraceCompression) -> Builder {
+        self.traces_cfgs.compression = Some(compression);
+        self
+    }
+
+    /// Sets the static headers added to every export request.
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Builder {
+        self.traces_cfgs.headers = headers;
+        self
+    }
+
+    /// Sets the TLS configuration of the export channel, replacing the configured PEM files.
+    pub fn tls(mut self, tls: ClientTlsConfig) -> Builder {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Sets the sampler, replacing the environment-driven sampler.
+    ///
+    /// Spans started within `helpers::suppress` are still dropped.
+    pub fn sampler(mut self, sampler: Sampler) -> Builder {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Sets the trace and span ID generator, replacing the one selected by `TRACES_ID_GENERATOR`.
+    ///
+    /// The generator is wrapped in a `ValidatedIdGenerator` so invalid identifiers are
+    /// never emitted. See `exporters::id_generator` for guidance on implementing one.
+    pub fn id_generator<G: IdGenerator + 'static>(mut self, id_generator: G) -> Builder {
+        self.id_generator = Some(SharedIdGenerator(Arc::new(id_generator)));
+        self
+    }
+
+    /// Adds a custom span processor to the pipeline, e.g. to redact PII attributes.
+    ///
+    /// Custom processors are registered in the order they are added, before the exporting
+    /// processor, so `on_start` sees the span first and changes made to it there, such as
+    /// overwriting an attribute, are exported. `on_end` receives a copy of the finished span:
+    /// changes made to it are not exported, so redaction must happen in `on_start`, on the
+    /// attributes known at creation. The processor is shared by every provider built from
+    /// this builder and does not receive `set_resource`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use opentelemetry::{Context, KeyValue, trace::Span as _};
+    /// use opentelemetry_sdk::{
+    ///     error::OTelSdkResult,
+    ///     trace::{Span, SpanData, SpanProcessor},
+    /// };
+    /// use std::time::Duration;
+    /// use traces::exporters::otlp_grpc::Builder;
+    ///
+    /// #[derive(Debug)]
+    /// struct RedactEmail;
+    ///
+    /// impl SpanProcessor for RedactEmail {
+    ///     fn on_start(&self, span: &mut Span, _cx: &Context) {
+    ///         span.set_attribute(KeyValue::new("user.email", "[redacted]"));
+    ///     }
+    ///
+    ///     fn on_end(&self, _span: SpanData) {}
+    ///
+    ///     fn force_flush(&self) -> OTelSdkResult {
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let provider = Builder::new()
+    ///         .add_span_processor(Box::new(RedactEmail))
+    ///         .build()
+    ///         .expect("Failed to build OTLP tracer provider");
+    /// }
+    /// ```
+    pub fn add_span_processor(mut self, processor: Box<dyn SpanProcessor>) -> Builder {
+        self.processors.push(SharedProcessor(Arc::from(processor)));
+        self
+    }
+
+    /// Builds the tracer provider without registering it globally.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SdkTracerProvider)` if the exporter and the provider are built
+    /// * `Err(TracesError)` otherwise
+    pub fn build(&self) -> Result<SdkTracerProvider, TracesError> {
+        let id_generator = match &self.id_generator {
+            Some(id_generator) => id_generator.clone(),
+            None => id_generator::configured(self.traces_cfgs.id_generator),
+        };
+
+        self.build_with(id_generator, |exporter_builder| exporter_builder)
+            .map(|(provider, _)| provider)
+    }
+
+    /// Builds the tracer provider with an id generator and a customized exporter builder.
+    ///
+    /// The ratio sampler of the provider is returned along with it, to be registered when the
+    /// provider is installed.
+    fn build_with<G, F>(
+        &self,
+        id_generator: G,
+        customize: F,
+    ) -> Result<(SdkTracerProvider, ReloadableRatioSampler), TracesError>
+    where
+        G: IdGenerator + 'static,
+        F: Fn(
+            SpanExporterBuilder<TonicExporterBuilderSet>,
+        ) -> SpanExporterBuilder<TonicExporterBuilderSet>,
     {
-        Ok(p) => Ok(p),
-        Err(err) => {
-            error!(
-                error = err.to_string(),
-                "failure to create exporter provider"
-            );
-            Err(TracesError::ExporterProviderError)
-        }
-    }?;
-
-    let provider = TracerProviderBuilder::default()
-        .with_sampler(get_sampler(&app_cfgs, &otlp_cfgs))
-        .with_id_generator(RandomIdGenerator::default())
-        .with_max_events_per_span(64)
-        .with_max_attributes_per_span(16)
-        .with_resource(
-            Resource::builder()
-                .with_service_name(app_cfgs.name.clone())
-                .with_attribute(KeyValue::new(
-                    "service.namespace",
-                    format!("{}", app_cfgs.namespace),
-                ))
-                .with_attribute(KeyValue::new("environment", format!("{}", app_cfgs.env)))
-                .with_attribute(KeyValue::new("library.language", "rust"))
-                .build(),
+        validate_endpoint(&self.otlp_cfgs.endpoint)?;
+
+        let (_, connect_timeout) = self.timeouts();
+
+        let exporter = with_retries(
+            self.traces_cfgs.connect_retries,
+            self.traces_cfgs.connect_backoff,
+            || {
+                // The export channel connects lazily, so an unreachable collector is only
+                // detected at build time by probing it
+                if self.traces_cfgs.connect_retries > 0 {
+                    connect(&self.otlp_cfgs.endpoint, connect_timeout)?;
+                }
+
+                customize(self.exporter_builder()?)
+                    .build()
+                    .map_err(|err| TracesError::ExporterProviderError(err.to_string()))
+            },
+        )?;
+
+        let (mut builder, ratio_sampler) = provider_builder(
+            id_generator,
+            &self.app_cfgs,
+            &self.otlp_cfgs,
+            &self.traces_cfgs,
+        );
+
+        if let Some(sampler) = &self.sampler {
+            builder = builder.with_sampler(SuppressingSampler::new(sampler.clone()));
+        }
+
+        for processor in &self.processors {
+            builder = builder.with_span_processor(processor.clone());
+        }
+
+        let builder = processors::batch::with_exporter(builder, exporter, &self.traces_cfgs)?;
+
+        let provider = processors::with_processors(builder, &self.traces_cfgs).build();
+
+        Ok((provider, ratio_sampler))
+    }
+
+    /// Registers the tracer provider, its ratio sampler and the configured propagators globally.
+    fn register(
+        &self,
+        provider: SdkTracerProvider,
+        ratio_sampler: ReloadableRatioSampler,
+    ) -> SdkTracerProvider {
+        global::set_tracer_provider(provider.clone());
+        sampler::register_ratio_sampler(ratio_sampler);
+        propagation::install(&self.traces_cfgs.propagators);
+
+        info!("traces::install otlp tracer installed");
+
+        provider
+    }
+
+    /// Resolves the timeouts of the export channel.
+    ///
+    /// # Returns
+    ///
+    /// The timeout of each export request, applied to the exporter and the channel, and the
+    /// timeout of the connection establishment, applied to the channel and the connectivity
+    /// probe; each falls back to `OTLPConfigs::exporter_timeout` when not set
+    fn timeouts(&self) -> (Duration, Duration) {
+        let fallback = self.otlp_cfgs.exporter_timeout;
+
+        (
+            self.traces_cfgs.export_timeout.unwrap_or(fallback),
+            self.traces_cfgs.connect_timeout.unwrap_or(fallback),
         )
-        .with_batch_exporter(exporter)
-        .build();
+    }
+
+    /// Creates the exporter builder configured from the OTLP settings.
+    fn exporter_builder(
+        &self,
+    ) -> Result<SpanExporterBuilder<TonicExporterBuilderSet>, TracesError> {
+        let (endpoint, secure) = transport(&self.otlp_cfgs.endpoint, self.traces_cfgs.insecure);
+
+        let (export_timeout, connect_timeout) = self.timeouts();
+
+        // The channel is built here as the exporter builder has no connect timeout setting
+        let mut channel = Channel::from_shared(endpoint.clone())
+            .map_err(|err| {
+                error!(error = err.to_string(), "invalid OTLP endpoint");
+                TracesError::InvalidConfigError(format!("invalid OTLP endpoint {endpoint}: {err}"))
+            })?
+            .connect_timeout(connect_timeout)
+            .timeout(export_timeout);
+
+        if secure {
+            let tls = match &self.tls {
+                Some(tls) => tls.clone(),
+                None => tls_config(&self.traces_cfgs)?
+                    .unwrap_or_else(|| ClientTlsConfig::new().with_native_roots()),
+            };
+
+            channel = channel.tls_config(tls).map_err(|err| {
+                error!(error = err.to_string(), "failure to configure TLS");
+                TracesError::TlsConfigError(err.to_string())
+            })?;
+        } else if self.tls.is_some()
+            || self.traces_cfgs.ca_cert_path.is_some()
+            || self.traces_cfgs.client_cert_path.is_some()
+        {
+            warn!("TLS is configured but the OTLP export channel is plaintext, ignoring it");
+        }
+
+        let compression = export_compression(&endpoint, self.traces_cfgs.compression);
+
+        let mut exporter_builder = SpanExporter::builder()
+            .with_tonic()
+            .with_protocol(Protocol::Grpc)
+            .with_timeout(export_timeout)
+            .with_endpoint(endpoint)
+            .with_channel(channel.connect_lazy());
+
+        if let Some(compression) = compression.to_otlp() {
+            exporter_builder = exporter_builder.with_compression(compression);
+        }
+
+        if !self.traces_cfgs.headers.is_empty() {
+            exporter_builder = exporter_builder.with_metadata(metadata(&self.traces_cfgs.headers));
+        }
+
+        Ok(exporter_builder)
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder::new()
+    }
+}
+
+/// Resolves the compression of the export requests.
+///
+/// The configured compression takes precedence; otherwise requests to a loopback endpoint
+/// are uncompressed and other requests are compressed with gzip.
+fn export_compression(endpoint: &str, configured: Option<TraceCompression>) -> TraceCompression {
+    let compression = configured.unwrap_or_else(|| {
+        if is_loopback(endpoint) {
+            TraceCompression::None
+        } else {
+            TraceCompression::Gzip
+        }
+    });
+
+    info!(
+        compression = ?compression,
+        configured = configured.is_some(),
+        "traces::install otlp export compression"
+    );
+
+    compression
+}
+
+/// Returns whether the host of an endpoint is `localhost` or a loopback address.
+fn is_loopback(endpoint: &str) -> bool {
+    let Some(host) = endpoint
+        .parse::<http::Uri>()
+        .ok()
+        .and_then(|uri| uri.host().map(str::to_owned))
+    else {
+        return false;
+    };
+
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<IpAddr>()
+            .is_ok_and(|address| address.is_loopback())
+}
+
+/// Resolves the endpoint and whether the export channel uses TLS.
+///
+/// The transport security follows the endpoint scheme, `https` meaning TLS, unless the
+/// `insecure` flag is set, in which case the scheme is rewritten to match it.
+fn transport(endpoint: &str, insecure: Option<bool>) -> (String, bool) {
+    let endpoint = endpoint.trim();
+    let is_https = endpoint
+        .get(..8)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("https://"));
+
+    let secure = insecure.map_or(is_https, |insecure| !insecure);
+
+    let endpoint = match (secure, is_https) {
+        (true, false) => match endpoint.split_once("://") {
+            Some((_, rest)) => format!("https://{rest}"),
+            None => format!("https://{endpoint}"),
+        },
+        (false, true) => format!("http://{}", &endpoint[8..]),
+        _ => endpoint.to_owned(),
+    };
+
+    info!(
+        endpoint = endpoint,
+        tls = secure,
+        "traces::install otlp export channel {}",
+        if secure { "uses TLS" } else { "is plaintext" }
+    );
+
+    (endpoint, secure)
+}
+
+/// Converts the configured headers into gRPC metadata, skipping invalid names and values.
+fn metadata(headers: &HashMap<String, String>) -> MetadataMap {
+    let mut metadata = MetadataMap::with_capacity(headers.len());
+
+    for (name, value) in headers {
+        let Ok(key) = MetadataKey::from_bytes(name.as_bytes()) else {
+            warn!(
+                header = name.as_str(),
+                "invalid export header name, skipping"
+            );
+            continue;
+        };
+
+        let Ok(value) = MetadataValue::try_from(value.as_str()) else {
+            warn!(
+                header = name.as_str(),
+                "invalid export header value, skipping"
+            );
+            continue;
+        };
+
+        metadata.insert(key, value);
+    }
+
+    metadata
+}
+
+/// Checks that the collector endpoint is an absolute URI with a scheme and a host.
+///
+/// An invalid endpoint would otherwise only surface when the first batch is exported.
+fn validate_endpoint(endpoint: &str) -> Result<(), TracesError> {
+    let endpoint = endpoint.trim();
+    if endpoint.is_empty() {
+        error!("OTLP endpoint is not configured");
+        return Err(TracesError::InvalidConfigError(
+            "the OTLP endpoint is empty".into(),
+        ));
+    }
+
+    let uri = endpoint.parse::<http::Uri>().map_err(|err| {
+        error!(
+            error = err.to_string(),
+            endpoint = endpoint,
+            "invalid OTLP endpoint"
+        );
+        TracesError::InvalidConfigError(format!("invalid OTLP endpoint {endpoint}: {err}"))
+    })?;
+
+    if uri.scheme().is_none() || uri.host().is_none_or(str::is_empty) {
+        error!(endpoint = endpoint, "OTLP endpoint has no scheme or host");
+        return Err(TracesError::InvalidConfigError(format!(
+            "the OTLP endpoint {endpoint} must include a scheme and a host, e.g. http://localhost:4317"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Creates the TLS configuration of the export channel from the configured PEM files.
+///
+/// A CA certificate alone enables server-authenticated TLS; a client certificate and key
+/// additionally enable mutual TLS. Returns `None` when no TLS file is configured.
+fn tls_config(traces_cfgs: &TracesConfigs) -> Result<Option<ClientTlsConfig>, TracesError> {
+    let identity = match (&traces_cfgs.client_cert_path, &traces_cfgs.client_key_path) {
+        (Some(cert), Some(key)) => Some(Identity::from_pem(read_pem(cert)?, read_pem(key)?)),
+        (None, None) => None,
+        _ => {
+            return Err(TracesError::TlsConfigError(
+                "client certificate and key must be configured together".into(),
+            ));
+        }
+    };
+
+    if traces_cfgs.ca_cert_path.is_none() && identity.is_none() {
+        return Ok(None);
+    }
+
+    let mut tls_config = ClientTlsConfig::new().with_native_roots();
+
+    if let Some(ca) = &traces_cfgs.ca_cert_path {
+        tls_config = tls_config.ca_certificate(Certificate::from_pem(read_pem(ca)?));
+    }
+
+    if let Some(identity) = identity {
+        tls_config = tls_config.identity(identity);
+    }
+
+    Ok(Some(tls_config))
+}
+
+/// Reads a PEM file, checking that it holds at least one PEM block.
+fn read_pem(path: &str) -> Result<Vec<u8>, TracesError> {
+    let pem = fs::read(path).map_err(|err| {
+        error!(
+            error = err.to_string(),
+            path = path,
+            "failure to read TLS file"
+        );
+        TracesError::TlsConfigError(format!("{path}: {err}"))
+    })?;
+
+    if !String::from_utf8_lossy(&pem).contains("-----BEGIN ") {
+        error!(path = path, "TLS file is not PEM encoded");
+        return Err(TracesError::TlsConfigError(format!(
+            "{path}: not a PEM encoded file"
+        )));
+    }
+
+    Ok(pem)
+}
+
+/// Runs an attempt, retrying with exponential backoff and logging the failures if any.
+///
+/// Up to `retries` retries are made, waiting `backoff` before the first one and doubling the
+/// wait after each attempt. The calling thread is blocked while waiting. Only unreachable
+/// collectors and exporter build failures are retried; configuration errors, e.g. invalid
+/// TLS files, are returned right away.
+///
+/// With retries enabled, the OTLP gRPC exporter build probes the collector first, as its
+/// export channel connects lazily and would otherwise build even when the collector is down.
+fn with_retries<T, F>(retries: u32, backoff: Duration, mut attempt: F) -> Result<T, TracesError>
+where
+    F: FnMut() -> Result<T, TracesError>,
+{
+    let mut backoff = backoff;
+    let mut retried = 0;
+
+    loop {
+        match attempt() {
+            Ok(value) => return Ok(value),
+            Err(
+                err @ (TracesError::CollectorUnreachableError(_)
+                | TracesError::ExporterProviderError(_)),
+            ) => {
+                if retried == retries {
+                    error!(
+                        error = err.to_string(),
+                        retries = retries,
+                        "failure to create exporter provider"
+                    );
+                    return Err(err);
+                }
+
+                retried += 1;
+                warn!(
+                    error = err.to_string(),
+                    attempt = retried,
+                    retries = retries,
+                    "failure to create exporter provider, retrying"
+                );
+                thread::sleep(backoff);
+                backoff = backoff.saturating_mul(2);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{Span, Tracer, TracerProvider};
+    use std::net::TcpListener;
+
+    #[test]
+    fn retries_until_the_attempt_succeeds() {
+        let mut attempts = 0;
+
+        let result = with_retries(5, Duration::ZERO, || {
+            attempts += 1;
+            if attempts <= 3 {
+                return Err(TracesError::CollectorUnreachableError("down".into()));
+            }
+            Ok(attempts)
+        });
+
+        assert_eq!(result, Ok(4));
+        assert_eq!(attempts, 4);
+    }
+
+    #[test]
+    fn gives_up_after_the_retries() {
+        let mut attempts = 0;
+
+        let result: Result<(), TracesError> = with_retries(2, Duration::ZERO, || {
+            attempts += 1;
+            Err(TracesError::ExporterProviderError("down".into()))
+        });
+
+        assert_eq!(
+            result,
+            Err(TracesError::ExporterProviderError("down".into()))
+        );
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn does_not_retry_configuration_errors() {
+        let mut attempts = 0;
+
+        let result: Result<(), TracesError> = with_retries(2, Duration::ZERO, || {
+            attempts += 1;
+            Err(TracesError::TlsConfigError("invalid".into()))
+        });
+
+        assert_eq!(result, Err(TracesError::TlsConfigError("invalid".into())));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn connects_to_a_listening_collector() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        assert_eq!(connect(&endpoint, Duration::from_secs(1)), Ok(()));
+    }
+
+    #[test]
+    fn reports_a_collector_that_is_down() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        assert!(matches!(
+            connect(&endpoint, Duration::from_secs(1)),
+            Err(TracesError::CollectorUnreachableError(_))
+        ));
+    }
+
+    /// Returns the path of a TLS fixture, generated for these tests only.
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/tls/{name}", env!("CARGO_MANIFEST_DIR"))
+    }
+
+    /// Builds a configuration with the named TLS fixtures, an empty name leaving a file unset.
+    fn tls_cfgs(ca: &str, cert: &str, key: &str) -> TracesConfigs {
+        let path = |name: &str| (!name.is_empty()).then(|| fixture(name));
+
+        TracesConfigs {
+            ca_cert_path: path(ca),
+            client_cert_path: path(cert),
+            client_key_path: path(key),
+            ..TracesConfigs::default()
+        }
+    }
+
+    #[test]
+    fn builds_no_tls_config_without_files() {
+        assert!(matches!(tls_config(&tls_cfgs("", "", "")), Ok(None)));
+    }
+
+    #[test]
+    fn builds_server_authenticated_tls_from_a_ca() {
+        let tls = tls_config(&tls_cfgs("ca.pem", "", ""))
+            .expect("valid TLS files")
+            .expect("TLS config");
+
+        assert!(
+            Channel::from_static("https://collector:4317")
+                .tls_config(tls)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn builds_mutual_tls_from_a_client_certificate_and_key() {
+        let tls = tls_config(&tls_cfgs("ca.pem", "client.pem", "client.key"))
+            .expect("valid TLS files")
+            .expect("TLS config");
+
+        assert!(
+            Channel::from_static("https://collector:4317")
+                .tls_config(tls)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_a_client_certificate_without_key() {
+        assert!(matches!(
+            tls_config(&tls_cfgs("ca.pem", "client.pem", "")),
+            Err(TracesError::TlsConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_and_non_pem_files() {
+        for ca in ["missing.pem", "invalid.pem"] {
+            assert!(matches!(
+                tls_config(&tls_cfgs(ca, "", "")),
+                Err(TracesError::TlsConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn skips_invalid_export_headers() {
+        let headers = HashMap::from([
+            ("x-tenant".to_owned(), "acme".to_owned()),
+            ("invalid name".to_owned(), "value".to_owned()),
+            ("x-invalid-value".to_owned(), "line\nbreak".to_owned()),
+        ]);
+
+        let metadata = metadata(&headers);
+
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(
+            metadata
+                .get("x-tenant")
+                .and_then(|value| value.to_str().ok()),
+            Some("acme")
+        );
+    }
+
+    #[test]
+    fn falls_back_to_gzip_for_remote_collectors() {
+        assert_eq!(
+            export_compression("http://collector:4317", None),
+            TraceCompression::Gzip
+        );
+    }
+
+    #[test]
+    fn skips_compression_for_loopback_collectors() {
+        for endpoint in [
+            "http://localhost:4317",
+            "http://127.0.0.1:4317",
+            "http://[::1]:4317",
+        ] {
+            assert_eq!(export_compression(endpoint, None), TraceCompression::None);
+        }
+    }
+
+    #[test]
+    fn keeps_the_configured_compression() {
+        assert_eq!(
+            export_compression("http://localhost:4317", Some(TraceCompression::Zstd)),
+            TraceCompression::Zstd
+        );
+    }
+
+    #[test]
+    fn rejects_an_empty_endpoint() {
+        for endpoint in ["", "  "] {
+            assert!(matches!(
+                validate_endpoint(endpoint),
+                Err(TracesError::InvalidConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_an_endpoint_without_scheme() {
+        for endpoint in ["collector:4317", "localhost"] {
+            assert!(matches!(
+                validate_endpoint(endpoint),
+                Err(TracesError::InvalidConfigError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn accepts_an_absolute_endpoint() {
+        for endpoint in [
+            "http://localhost:4317",
+            "https://collector.example.com",
+            " http://[::1]:4317 ",
+        ] {
+            assert_eq!(validate_endpoint(endpoint), Ok(()));
+        }
+    }
+
+    #[test]
+    fn uses_plaintext_for_http_endpoints() {
+        assert_eq!(
+            transport("http://collector:4317", None),
+            ("http://collector:4317".to_owned(), false)
+        );
+    }
+
+    #[test]
+    fn uses_tls_for_https_endpoints() {
+        assert_eq!(
+            transport("HTTPS://collector:4317", None),
+            ("HTTPS://collector:4317".to_owned(), true)
+        );
+    }
+
+    #[test]
+    fn rewrites_the_scheme_to_match_the_insecure_flag() {
+        assert_eq!(
+            transport("https://collector:4317", Some(true)),
+            ("http://collector:4317".to_owned(), false)
+        );
+        assert_eq!(
+            transport("http://collector:4317", Some(false)),
+            ("https://collector:4317".to_owned(), true)
+        );
+        assert_eq!(
+            transport("collector:4317", Some(false)),
+            ("https://collector:4317".to_owned(), true)
+        );
+    }
+
+    #[test]
+    fn builder_keeps_the_explicit_settings() {
+        let builder = Builder::new()
+            .endpoint("http://collector:4317")
+            .timeout(Duration::from_secs(3))
+            .connect_timeout(Duration::from_secs(1))
+            .compression(TraceCompression::Zstd)
+            .headers(HashMap::from([("x-tenant".to_owned(), "acme".to_owned())]));
+
+        assert_eq!(builder.otlp_cfgs.endpoint, "http://collector:4317");
+        assert_eq!(
+            builder.traces_cfgs.export_timeout,
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            builder.traces_cfgs.connect_timeout,
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            export_compression(&builder.otlp_cfgs.endpoint, builder.traces_cfgs.compression),
+            TraceCompression::Zstd
+        );
+        assert_eq!(
+            metadata(&builder.traces_cfgs.headers)
+                .get("x-tenant")
+                .and_then(|value| value.to_str().ok()),
+            Some("acme")
+        );
+    }
+
+    #[test]
+    fn builds_a_provider_with_the_explicit_sampler() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime built");
+        let _guard = runtime.enter();
+
+        let provider = Builder::new()
+            .endpoint("http://localhost:4317")
+            .sampler(Sampler::AlwaysOff)
+            .build()
+            .expect("provider built");
+        let span = provider.tracer("test").start("root");
+
+        assert!(!span.span_context().is_sampled());
+    }
+
+    #[test]
+    fn does_not_build_a_provider_without_endpoint() {
+        assert!(matches!(
+            Builder::new().endpoint("").build(),
+            Err(TracesError::InvalidConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn resolves_the_export_and_connect_timeouts_separately() {
+        let builder = Builder::new()
+            .timeout(Duration::from_secs(3))
+            .connect_timeout(Duration::from_secs(30));
+
+        assert_eq!(
+            builder.timeouts(),
+            (Duration::from_secs(3), Duration::from_secs(30))
+        );
+    }
 
-    global::set_tracer_provider(provider.clone());
-    global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
-        Box::new(TraceContextPropagator::new()),
-        Box::new(BaggagePropagator::new()),
-    ]));
+    #[test]
+    fn falls_back_to_the_exporter_timeout() {
+        let mut builder = Builder::new();
+        builder.traces_cfgs.export_timeout = None;
+        builder.traces_cfgs.connect_timeout = None;
+        builder.otlp_cfgs.exporter_timeout = Duration::from_secs(7);
 
-    info!("traces::install otlp tracer installed");
+        assert_eq!(
+            builder.timeouts(),
+            (Duration::from_secs(7), Duration::from_secs(7))
+        );
 
-    Ok(provider)
+        let builder = builder.connect_timeout(Duration::from_secs(30));
+        assert_eq!(
+            builder.timeouts(),
+            (Duration::from_secs(7), Duration::from_secs(30))
+        );
+    }
 }
@@ -0,0 +1,106 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! OTLP HTTP exporter implementation.
+//!
+//! This module provides functionality to export trace data using the OpenTelemetry Protocol (OTLP)
+//! over HTTP with binary protobuf payloads. This exporter is suitable for environments where
+//! gRPC is not available, e.g. behind ingresses that only allow HTTP/1.1.
+
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::{provider_builder, sampler},
+    processors, propagation,
+};
+use configs::{app::AppConfigs, otlp::OTLPConfigs};
+use opentelemetry::global;
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator, SdkTracerProvider};
+use tracing::{error, info};
+
+/// Installs the OTLP HTTP exporter for OpenTelemetry tracing.
+///
+/// This function configures and installs an HTTP-based exporter that sends trace data
+/// to an OpenTelemetry collector or compatible backend.
+///
+/// The endpoint is `TracesConfigs::http_endpoint` when set, `OTLPConfigs::endpoint`
+/// otherwise. It must be the full traces URL, e.g. `http://localhost:4318/v1/traces`.
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError)` if installation fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::exporters::otlp_http;
+///
+/// fn main() {
+///     let provider = otlp_http::install().expect("Failed to install OTLP exporter");
+/// }
+/// ```
+pub fn install() -> Result<SdkTracerProvider, TracesError> {
+    install_with_id_generator(RandomIdGenerator::default())
+}
+
+/// Installs the OTLP HTTP exporter using a custom trace and span ID generator.
+///
+/// The generator is wrapped in a `ValidatedIdGenerator` so invalid identifiers
+/// are never emitted. See `exporters::id_generator` for guidance on implementing one.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError)` if installation fails
+pub fn install_with_id_generator<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
+    let app_cfgs = AppConfigs::new();
+    let otlp_cfgs = OTLPConfigs::new();
+    let traces_cfgs = TracesConfigs::new();
+
+    let endpoint = traces_cfgs
+        .http_endpoint
+        .clone()
+        .unwrap_or_else(|| otlp_cfgs.endpoint.clone());
+
+    let exporter = match SpanExporter::builder()
+        .with_http()
+        .with_protocol(Protocol::HttpBinary)
+        .with_timeout(otlp_cfgs.exporter_timeout)
+        .with_endpoint(endpoint)
+        .with_headers(traces_cfgs.headers.clone())
+        .build()
+    {
+        Ok(p) => Ok(p),
+        Err(err) => {
+            error!(
+                error = err.to_string(),
+                "failure to create exporter provider"
+            );
+            Err(TracesError::ExporterProviderError(err.to_string()))
+        }
+    }?;
+
+    let (builder, ratio_sampler) =
+        provider_builder(id_generator, &app_cfgs, &otlp_cfgs, &traces_cfgs);
+
+    let builder = processors::batch::with_exporter(builder, exporter, &traces_cfgs)?;
+
+    let provider = processors::with_processors(builder, &traces_cfgs).build();
+
+    global::set_tracer_provider(provider.clone());
+    sampler::register_ratio_sampler(ratio_sampler);
+    propagation::install(&traces_cfgs.propagators);
+
+    info!("traces::install otlp http tracer installed");
+
+    Ok(provider)
+}
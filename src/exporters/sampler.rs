@@ -7,28 +7,691 @@
 //! This module provides functionality to configure trace sampling strategies
 //! based on environment and application configuration.
 
+use crate::config::TracesConfigs;
+use crate::{errors::TracesError, helpers};
 use configs::{app::AppConfigs, otlp::OTLPConfigs};
-use opentelemetry_sdk::trace::Sampler;
+use opentelemetry::{
+    Context, KeyValue,
+    baggage::BaggageExt,
+    trace::{
+        Link, SamplingDecision, SamplingResult, SpanKind, TraceContextExt, TraceId, TraceState,
+    },
+};
+use opentelemetry_sdk::trace::{Sampler, ShouldSample};
+use std::{
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::warn;
 
 /// Returns a trace sampler configured based on application environment and settings.
 ///
 /// This function determines the appropriate sampling strategy:
+/// - In the environments listed in `always_off_environments`, it uses AlwaysOff sampling,
+///   keeping the pipeline installed while exporting nothing
 /// - In local environments, it uses AlwaysOn sampling for complete visibility
-/// - In other environments, it uses a parent-based sampling strategy with a configurable ratio
+/// - Otherwise, the standard `OTEL_TRACES_SAMPLER`/`OTEL_TRACES_SAMPLER_ARG` variables are
+///   honored when set to a supported sampler
+/// - Otherwise, it uses a parent-based sampling strategy with a configurable ratio, which
+///   can be changed at runtime with `provider::set_sampling_ratio` once the provider is
+///   installed
+/// - When a session baggage key is configured, root spans carrying that baggage entry are
+///   sampled per session with the same ratio
+/// - With `SamplerKind::RecordOnly`, the spans not sampled are still recorded, see
+///   `RecordingSampler`
 ///
 /// # Arguments
 ///
 /// * `app` - Application configuration containing environment settings
 /// * `otlp` - OpenTelemetry configuration containing sampling rate settings
+/// * `traces` - Tracing configuration containing the session baggage key and the sampling
+///   ratio, which overrides the OTLP sampling rate when set
 ///
 /// # Returns
 ///
-/// A configured `Sampler` instance appropriate for the environment
-pub(crate) fn get_sampler(app: &AppConfigs, otlp: &OTLPConfigs) -> Sampler {
+/// A configured sampler appropriate for the environment, honoring `helpers::suppress`, and
+/// the ratio sampler of the provider, to register with `register_ratio_sampler` when the
+/// provider is installed
+pub(crate) fn get_sampler(
+    app: &AppConfigs,
+    otlp: &OTLPConfigs,
+    traces: &TracesConfigs,
+) -> (SuppressingSampler, ReloadableRatioSampler) {
+    let ratio = traces.sampling_ratio.unwrap_or(otlp.exporter_rate_base);
+    // Each provider has its own ratio, so building a provider never resets the ratio set at
+    // runtime on the installed one
+    let ratio_sampler = ReloadableRatioSampler::new(ratio);
+
+    (
+        build_sampler(app, traces, ratio, &ratio_sampler),
+        ratio_sampler,
+    )
+}
+
+/// Builds the sampler selected by the configuration, see `get_sampler`.
+fn build_sampler(
+    app: &AppConfigs,
+    traces: &TracesConfigs,
+    ratio: f64,
+    ratio_sampler: &ReloadableRatioSampler,
+) -> SuppressingSampler {
+    let env = format!("{}", app.env);
+    if traces
+        .always_off_environments
+        .iter()
+        .any(|always_off| always_off.eq_ignore_ascii_case(&env))
+    {
+        return SuppressingSampler::new(Sampler::AlwaysOff);
+    }
+
     if app.env.is_local() {
-        return Sampler::AlwaysOn;
+        return SuppressingSampler::new(Sampler::AlwaysOn);
+    }
+
+    let sampler = match (standard_sampler(traces, ratio), &traces.session_baggage_key) {
+        (Some(sampler), _) => sampler,
+        (None, Some(key)) => Sampler::ParentBased(Box::new(SessionSampler::new(
+            key.clone(),
+            ratio,
+            Sampler::TraceIdRatioBased(ratio),
+        ))),
+        (None, None) => Sampler::ParentBased(Box::new(ratio_sampler.clone())),
+    };
+
+    match traces.sampler_kind {
+        SamplerKind::Sample => SuppressingSampler::new(sampler),
+        SamplerKind::RecordOnly => SuppressingSampler::new(RecordingSampler::new(sampler)),
+    }
+}
+
+/// How the spans not selected by the sampler are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SamplerKind {
+    /// The spans not sampled are dropped.
+    #[default]
+    Sample,
+    /// The spans not sampled are recorded but not exported, see `RecordingSampler`.
+    RecordOnly,
+}
+
+impl FromStr for SamplerKind {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sample" => Ok(SamplerKind::Sample),
+            "record_only" => Ok(SamplerKind::RecordOnly),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// Builds the sampler selected by the standard `OTEL_TRACES_SAMPLER` variable, if any.
+///
+/// The ratio of the `traceidratio` samplers is read from `OTEL_TRACES_SAMPLER_ARG`, falling
+/// back to the configured ratio when the argument is missing or malformed. Unsupported
+/// samplers are ignored with a warning.
+fn standard_sampler(traces: &TracesConfigs, default_ratio: f64) -> Option<Sampler> {
+    let name = traces.otel_sampler.as_deref()?;
+
+    let ratio = || match traces.otel_sampler_arg.as_deref().map(parse_sampling_ratio) {
+        Some(Ok(ratio)) => ratio,
+        Some(Err(err)) => {
+            warn!(
+                error = err.to_string(),
+                "invalid OTEL_TRACES_SAMPLER_ARG, using the configured ratio"
+            );
+            default_ratio
+        }
+        None => default_ratio,
+    };
+
+    match name.trim().to_lowercase().as_str() {
+        "always_on" => Some(Sampler::AlwaysOn),
+        "always_off" => Some(Sampler::AlwaysOff),
+        "traceidratio" => Some(Sampler::TraceIdRatioBased(ratio())),
+        "parentbased_always_on" => Some(Sampler::ParentBased(Box::new(Sampler::AlwaysOn))),
+        "parentbased_always_off" => Some(Sampler::ParentBased(Box::new(Sampler::AlwaysOff))),
+        "parentbased_traceidratio" => Some(Sampler::ParentBased(Box::new(
+            Sampler::TraceIdRatioBased(ratio()),
+        ))),
+        _ => {
+            warn!(
+                sampler = name,
+                "unsupported OTEL_TRACES_SAMPLER, using the configured sampler"
+            );
+            None
+        }
+    }
+}
+
+/// Parses a sampling ratio given as a fraction or a percentage.
+///
+/// Fractions such as `0.1` and percentages such as `10%` are accepted and normalized to a
+/// ratio between 0.0 and 1.0. Values above 1 without a `%` suffix, such as `10`, are rejected
+/// as ambiguous rather than read as 1000%.
+///
+/// # Arguments
+///
+/// * `value` - The sampling ratio to parse
+///
+/// # Returns
+///
+/// * `Ok(f64)` with the normalized ratio
+/// * `Err(TracesError::InvalidConfigError)` if the value is malformed, ambiguous or out of range
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::exporters::sampler::parse_sampling_ratio;
+///
+/// assert_eq!(parse_sampling_ratio("10%"), Ok(0.1));
+/// assert_eq!(parse_sampling_ratio("0.1"), Ok(0.1));
+/// assert!(parse_sampling_ratio("10").is_err());
+/// ```
+pub fn parse_sampling_ratio(value: &str) -> Result<f64, TracesError> {
+    let value = value.trim();
+    let (number, percentage) = match value.strip_suffix('%') {
+        Some(number) => (number.trim(), true),
+        None => (value, false),
+    };
+
+    let parsed: f64 = number.parse().map_err(|_| {
+        TracesError::InvalidConfigError(format!(
+            "sampling ratio \"{value}\" is neither a fraction nor a percentage"
+        ))
+    })?;
+
+    let ratio = if percentage { parsed / 100.0 } else { parsed };
+
+    if !percentage && parsed > 1.0 {
+        return Err(TracesError::InvalidConfigError(format!(
+            "sampling ratio \"{value}\" is ambiguous, use a fraction (0.1) or a percentage (10%)"
+        )));
+    }
+
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err(TracesError::InvalidConfigError(format!(
+            "sampling ratio \"{value}\" is out of range, expected 0-1 or 0%-100%"
+        )));
+    }
+
+    Ok(ratio)
+}
+
+/// A sampler dropping the spans started in a scope suppressed by `helpers::suppress`.
+///
+/// Spans outside suppressed scopes are delegated to the wrapped sampler.
+#[derive(Debug, Clone)]
+pub struct SuppressingSampler {
+    inner: Box<dyn ShouldSample>,
+}
+
+impl SuppressingSampler {
+    /// Creates a new `SuppressingSampler` wrapping the provided sampler.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The sampler used outside suppressed scopes
+    ///
+    /// # Returns
+    ///
+    /// A new `SuppressingSampler` instance
+    pub fn new<S: ShouldSample + 'static>(inner: S) -> SuppressingSampler {
+        SuppressingSampler {
+            inner: Box::new(inner),
+        }
+    }
+}
+
+impl ShouldSample for SuppressingSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        if parent_context.is_some_and(helpers::is_suppressed) {
+            return SamplingResult {
+                decision: SamplingDecision::Drop,
+                attributes: Vec::new(),
+                trace_state: parent_context
+                    .map(|cx| cx.span().span_context().trace_state().clone())
+                    .unwrap_or_default(),
+            };
+        }
+
+        self.inner
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+}
+
+/// A sampler recording every span while only sampling the spans selected by its inner sampler.
+///
+/// Spans dropped by the inner sampler are `RecordOnly` instead: they are recording, so their
+/// attributes and events are kept and `helpers::trace_id`/`helpers::span_id` return valid
+/// identifiers for logging, but they are not flagged as sampled and are therefore neither
+/// exported nor propagated as sampled to downstream services.
+#[derive(Debug, Clone)]
+pub struct RecordingSampler {
+    inner: Sampler,
+}
+
+impl RecordingSampler {
+    /// Creates a new `RecordingSampler` wrapping the provided sampler.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The sampler selecting the spans to export
+    ///
+    /// # Returns
+    ///
+    /// A new `RecordingSampler` instance
+    pub fn new(inner: Sampler) -> RecordingSampler {
+        RecordingSampler { inner }
+    }
+}
+
+impl ShouldSample for RecordingSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let mut result =
+            self.inner
+                .should_sample(parent_context, trace_id, name, span_kind, attributes, links);
+
+        if result.decision == SamplingDecision::Drop {
+            result.decision = SamplingDecision::RecordOnly;
+        }
+
+        result
+    }
+}
+
+/// Scale of the fixed-point sampling ratio stored by `ReloadableRatioSampler`.
+const RATIO_SCALE: u64 = 1_000_000_000;
+
+/// The ratio sampler of the installed tracer provider, updated by `provider::set_sampling_ratio`.
+static INSTALLED_RATIO_SAMPLER: Mutex<Option<ReloadableRatioSampler>> = Mutex::new(None);
+
+/// Registers the ratio sampler of the tracer provider being installed globally.
+///
+/// The ratio sampler of the previously installed provider is no longer updated.
+pub(crate) fn register_ratio_sampler(sampler: ReloadableRatioSampler) {
+    if let Ok(mut installed) = INSTALLED_RATIO_SAMPLER.lock() {
+        installed.replace(sampler);
+    }
+}
+
+/// Returns the ratio sampler of the installed tracer provider, if any.
+pub(crate) fn installed_ratio_sampler() -> Option<ReloadableRatioSampler> {
+    INSTALLED_RATIO_SAMPLER.lock().ok()?.clone()
+}
+
+/// A trace ID ratio sampler whose ratio can be changed while spans are being sampled.
+///
+/// The ratio is stored as a fixed-point value in an `Arc<AtomicU64>`, with a precision of
+/// 10^-9, and read on every sampling decision. Clones share the same ratio, so a clone kept
+/// aside can update the sampler registered on a tracer provider. Updates are atomic and
+/// lock-free, so `set_ratio` may be called from any thread; decisions already in progress
+/// on other threads may still use the previous ratio.
+#[derive(Debug, Clone)]
+pub struct ReloadableRatioSampler {
+    ratio: Arc<AtomicU64>,
+}
+
+impl ReloadableRatioSampler {
+    /// Creates a new `ReloadableRatioSampler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ratio` - The fraction of traces to sample, clamped between 0.0 and 1.0
+    ///
+    /// # Returns
+    ///
+    /// A new `ReloadableRatioSampler` instance
+    pub fn new(ratio: f64) -> ReloadableRatioSampler {
+        ReloadableRatioSampler {
+            ratio: Arc::new(AtomicU64::new(to_fixed_point(ratio))),
+        }
+    }
+
+    /// Returns the current sampling ratio.
+    pub fn ratio(&self) -> f64 {
+        self.ratio.load(Ordering::Relaxed) as f64 / RATIO_SCALE as f64
     }
 
-    let sampler = Sampler::TraceIdRatioBased(otlp.exporter_rate_base);
-    return Sampler::ParentBased(Box::new(sampler));
+    /// Sets the sampling ratio, clamped between 0.0 and 1.0, for this sampler and its clones.
+    pub fn set_ratio(&self, ratio: f64) {
+        self.ratio.store(to_fixed_point(ratio), Ordering::Relaxed);
+    }
+}
+
+/// Converts a ratio into its fixed-point representation, a NaN ratio being 0.
+fn to_fixed_point(ratio: f64) -> u64 {
+    (ratio.clamp(0.0, 1.0) * RATIO_SCALE as f64).round() as u64
+}
+
+impl ShouldSample for ReloadableRatioSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        Sampler::TraceIdRatioBased(self.ratio()).should_sample(
+            parent_context,
+            trace_id,
+            name,
+            span_kind,
+            attributes,
+            links,
+        )
+    }
+}
+
+/// A sampler keeping or dropping whole sessions.
+///
+/// The decision is derived deterministically from a session identifier read from the
+/// baggage of the parent context, so every trace of a session shares the same decision,
+/// across requests and across services using the same key and ratio. Spans without the
+/// session baggage entry are delegated to the fallback sampler.
+#[derive(Debug, Clone)]
+pub struct SessionSampler {
+    key: String,
+    ratio: f64,
+    fallback: Sampler,
+}
+
+impl SessionSampler {
+    /// Creates a new `SessionSampler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The baggage key holding the session identifier
+    /// * `ratio` - The fraction of sessions to sample, between 0.0 and 1.0
+    /// * `fallback` - The sampler used when the session identifier is absent
+    ///
+    /// # Returns
+    ///
+    /// A new `SessionSampler` instance
+    pub fn new(key: impl Into<String>, ratio: f64, fallback: Sampler) -> SessionSampler {
+        SessionSampler {
+            key: key.into(),
+            ratio,
+            fallback,
+        }
+    }
+
+    /// Returns whether a session is sampled, using a stable FNV-1a hash of its identifier.
+    fn is_sampled(&self, session: &str) -> bool {
+        let hash = session
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+
+        (hash as f64 / u64::MAX as f64) < self.ratio
+    }
+}
+
+impl ShouldSample for SessionSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let session = parent_context
+            .and_then(|cx| cx.baggage().get(self.key.as_str()).map(|v| v.to_string()));
+
+        let Some(session) = session else {
+            return self.fallback.should_sample(
+                parent_context,
+                trace_id,
+                name,
+                span_kind,
+                attributes,
+                links,
+            );
+        };
+
+        let decision = if self.is_sampled(&session) {
+            SamplingDecision::RecordAndSample
+        } else {
+            SamplingDecision::Drop
+        };
+
+        SamplingResult {
+            decision,
+            attributes: Vec::new(),
+            trace_state: parent_context
+                .map(|cx| cx.span().span_context().trace_state().clone())
+                .unwrap_or_else(TraceState::default),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{provider, test_utils};
+    use opentelemetry::trace::{Span, Tracer};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    /// Returns whether a root span started on the pipeline is sampled.
+    fn samples_root_span(pipeline: &test_utils::Pipeline) -> bool {
+        helpers::is_sampled(&helpers::ctx(&pipeline.tracer, SpanKind::Internal, "root"))
+    }
+
+    #[test]
+    fn set_sampling_ratio_updates_the_installed_provider() {
+        let _lock = test_utils::lock();
+        let ratio_sampler = ReloadableRatioSampler::new(0.0);
+        let pipeline = test_utils::pipeline_with(
+            SdkTracerProvider::builder()
+                .with_sampler(Sampler::ParentBased(Box::new(ratio_sampler.clone()))),
+        );
+        register_ratio_sampler(ratio_sampler);
+        assert!(!samples_root_span(&pipeline));
+
+        provider::set_sampling_ratio(1.0).expect("valid ratio");
+
+        assert!(samples_root_span(&pipeline));
+    }
+
+    #[test]
+    fn building_a_provider_keeps_the_installed_ratio() {
+        let _lock = test_utils::lock();
+        let installed = ReloadableRatioSampler::new(0.0);
+        register_ratio_sampler(installed.clone());
+        provider::set_sampling_ratio(1.0).expect("valid ratio");
+
+        let traces_cfgs = TracesConfigs {
+            sampling_ratio: Some(0.1),
+            ..TracesConfigs::default()
+        };
+        let (_sampler, built) = get_sampler(&AppConfigs::new(), &OTLPConfigs::new(), &traces_cfgs);
+
+        assert_eq!(installed.ratio(), 1.0);
+        assert_eq!(built.ratio(), 0.1);
+    }
+
+    #[test]
+    fn providers_do_not_share_the_ratio() {
+        let first = ReloadableRatioSampler::new(0.0);
+        let second = ReloadableRatioSampler::new(0.0);
+
+        first.set_ratio(1.0);
+
+        assert_eq!(first.ratio(), 1.0);
+        assert_eq!(second.ratio(), 0.0);
+        assert_eq!(first.clone().ratio(), 1.0);
+    }
+
+    #[test]
+    fn clamps_the_ratio() {
+        assert_eq!(ReloadableRatioSampler::new(2.0).ratio(), 1.0);
+        assert_eq!(ReloadableRatioSampler::new(-1.0).ratio(), 0.0);
+        assert_eq!(ReloadableRatioSampler::new(f64::NAN).ratio(), 0.0);
+    }
+
+    #[test]
+    fn set_sampling_ratio_rejects_out_of_range_ratios() {
+        assert!(provider::set_sampling_ratio(1.5).is_err());
+        assert!(provider::set_sampling_ratio(-0.1).is_err());
+    }
+
+    /// Builds a pipeline sampling every span, except in the listed environments.
+    fn pipeline_off_in(environments: Vec<String>) -> test_utils::Pipeline {
+        let traces_cfgs = TracesConfigs {
+            always_off_environments: environments,
+            sampling_ratio: Some(1.0),
+            ..TracesConfigs::default()
+        };
+        let (sampler, _) = get_sampler(&AppConfigs::new(), &OTLPConfigs::new(), &traces_cfgs);
+
+        test_utils::pipeline_with(SdkTracerProvider::builder().with_sampler(sampler))
+    }
+
+    #[test]
+    fn samples_nothing_in_an_always_off_environment() {
+        let env = format!("{}", AppConfigs::new().env);
+
+        let pipeline = pipeline_off_in(vec![env.to_uppercase()]);
+        assert!(!samples_root_span(&pipeline));
+
+        let pipeline = pipeline_off_in(vec![env.to_lowercase()]);
+        assert!(!samples_root_span(&pipeline));
+    }
+
+    #[test]
+    fn samples_outside_the_always_off_environments() {
+        let pipeline = pipeline_off_in(vec!["no-such-environment".to_string()]);
+
+        assert!(samples_root_span(&pipeline));
+    }
+
+    /// Returns the `Debug` output of the sampler selected by the standard variables, as
+    /// `Sampler` does not implement `PartialEq`.
+    fn standard(name: Option<&str>, arg: Option<&str>) -> Option<String> {
+        let traces_cfgs = TracesConfigs {
+            otel_sampler: name.map(str::to_string),
+            otel_sampler_arg: arg.map(str::to_string),
+            ..TracesConfigs::default()
+        };
+
+        standard_sampler(&traces_cfgs, 0.5).map(|sampler| format!("{sampler:?}"))
+    }
+
+    fn debug(sampler: Sampler) -> Option<String> {
+        Some(format!("{sampler:?}"))
+    }
+
+    #[test]
+    fn honors_each_otel_traces_sampler() {
+        let cases = [
+            ("always_on", Sampler::AlwaysOn),
+            ("always_off", Sampler::AlwaysOff),
+            ("traceidratio", Sampler::TraceIdRatioBased(0.25)),
+            (
+                "parentbased_always_on",
+                Sampler::ParentBased(Box::new(Sampler::AlwaysOn)),
+            ),
+            (
+                "parentbased_always_off",
+                Sampler::ParentBased(Box::new(Sampler::AlwaysOff)),
+            ),
+            (
+                "parentbased_traceidratio",
+                Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(0.25))),
+            ),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(
+                standard(Some(name), Some("0.25")),
+                debug(expected),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_the_otel_traces_sampler_case_insensitively() {
+        assert_eq!(
+            standard(Some(" ParentBased_Always_On "), None),
+            debug(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
+        );
+    }
+
+    #[test]
+    fn accepts_a_percentage_otel_traces_sampler_arg() {
+        assert_eq!(
+            standard(Some("traceidratio"), Some("25%")),
+            debug(Sampler::TraceIdRatioBased(0.25))
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_configured_ratio_on_a_malformed_arg() {
+        for arg in [None, Some("abc"), Some("10"), Some("")] {
+            assert_eq!(
+                standard(Some("traceidratio"), arg),
+                debug(Sampler::TraceIdRatioBased(0.5)),
+                "{arg:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn records_the_spans_not_sampled_without_exporting_them() {
+        let pipeline = test_utils::pipeline_with(
+            SdkTracerProvider::builder().with_sampler(RecordingSampler::new(Sampler::AlwaysOff)),
+        );
+
+        let mut span = pipeline.tracer.start("record-only");
+        assert!(span.is_recording());
+        assert!(!span.span_context().is_sampled());
+        span.end();
+
+        assert!(pipeline.spans().is_empty());
+    }
+
+    #[test]
+    fn exports_the_spans_sampled_by_the_wrapped_sampler() {
+        let pipeline = test_utils::pipeline_with(
+            SdkTracerProvider::builder().with_sampler(RecordingSampler::new(Sampler::AlwaysOn)),
+        );
+
+        let mut span = pipeline.tracer.start("sampled");
+        assert!(span.span_context().is_sampled());
+        span.end();
+
+        assert_eq!(pipeline.spans().len(), 1);
+    }
+
+    #[test]
+    fn ignores_an_unsupported_or_missing_otel_traces_sampler() {
+        assert_eq!(standard(Some("jaeger_remote"), Some("0.25")), None);
+        assert_eq!(standard(None, Some("0.25")), None);
+    }
 }
@@ -6,15 +6,35 @@
 //!
 //! This module provides functionality to export trace data to the standard output.
 //! This exporter is particularly useful for development and debugging environments
-//! where trace data can be viewed directly in the console.
+//! where trace data can be viewed directly in the console. Spans are pretty-printed by
+//! default, or written as one JSON object per line depending on `TRACES_STDOUT_FORMAT`,
+//! which keeps them grep-able when interleaved with the application logs.
 
-use crate::{errors::TracesError, exporters::sampler::get_sampler};
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::{StdoutFormat, json, provider_builder, sampler},
+    processors, propagation,
+};
 use configs::{app::AppConfigs, otlp::OTLPConfigs};
-use opentelemetry::{KeyValue, global, propagation::TextMapCompositePropagator};
+use opentelemetry::global;
 use opentelemetry_sdk::{
     Resource,
-    propagation::{BaggagePropagator, TraceContextPropagator},
-    trace::{RandomIdGenerator, SdkTracerProvider, TracerProviderBuilder},
+    error::{OTelSdkError, OTelSdkResult},
+    trace::{
+        IdGenerator, RandomIdGenerator, SdkTracerProvider, SimpleSpanProcessor, SpanData,
+        SpanExporter,
+    },
+};
+use serde_json::{Map, Value as Json};
+use std::{
+    fmt::Debug,
+    io::{self, Write},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
 };
 use tracing::info;
 
@@ -42,37 +62,241 @@ use tracing::info;
 /// }
 /// ```
 pub fn install() -> Result<SdkTracerProvider, TracesError> {
+    install_with_id_generator(RandomIdGenerator::default())
+}
+
+/// Installs the stdout exporter using a custom trace and span ID generator.
+///
+/// The generator is wrapped in a `ValidatedIdGenerator` so invalid identifiers
+/// are never emitted. See `exporters::id_generator` for guidance on implementing one.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if installation is successful
+/// * `Err(TracesError)` if installation fails
+pub fn install_with_id_generator<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
     let app_cfgs = AppConfigs::new();
     let otlp_cfgs = OTLPConfigs::new();
+    let traces_cfgs = TracesConfigs::new();
+
+    let (builder, ratio_sampler) =
+        provider_builder(id_generator, &app_cfgs, &otlp_cfgs, &traces_cfgs);
 
-    let exporter = opentelemetry_stdout::SpanExporter::default();
-
-    let provider = TracerProviderBuilder::default()
-        .with_sampler(get_sampler(&app_cfgs, &otlp_cfgs))
-        .with_id_generator(RandomIdGenerator::default())
-        .with_max_events_per_span(64)
-        .with_max_attributes_per_span(16)
-        .with_resource(
-            Resource::builder()
-                .with_service_name(app_cfgs.name.clone())
-                .with_attribute(KeyValue::new(
-                    "service.namespace",
-                    app_cfgs.namespace.clone(),
-                ))
-                .with_attribute(KeyValue::new("environment", format!("{}", app_cfgs.env)))
-                .with_attribute(KeyValue::new("library.language", "rust"))
-                .build(),
-        )
-        .with_simple_exporter(exporter)
-        .build();
+    let builder = match traces_cfgs.stdout_format {
+        StdoutFormat::Pretty => processors::with_exporting_processor(
+            builder,
+            SimpleSpanProcessor::new(opentelemetry_stdout::SpanExporter::default()),
+            &traces_cfgs,
+        ),
+        format => processors::with_exporting_processor(
+            builder,
+            SimpleSpanProcessor::new(JsonLinesExporter::new(io::stdout(), format)),
+            &traces_cfgs,
+        ),
+    };
+
+    let provider = processors::with_processors(builder, &traces_cfgs).build();
 
     global::set_tracer_provider(provider.clone());
-    global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
-        Box::new(TraceContextPropagator::new()),
-        Box::new(BaggagePropagator::new()),
-    ]));
+    sampler::register_ratio_sampler(ratio_sampler);
+    propagation::install(&traces_cfgs.propagators);
 
     info!("traces::install stdout tracer installed");
 
     Ok(provider)
 }
+
+/// A span exporter writing each span as a single line of JSON.
+///
+/// The exporter writes to any `Write` implementation, e.g. the standard output or a
+/// `Vec<u8>`, and flushes it after each batch. With `StdoutFormat::Compact` the resource
+/// attributes, identical for every span, are left out of the lines.
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::exporters::{StdoutFormat, stdout::JsonLinesExporter};
+///
+/// fn main() {
+///     let exporter = JsonLinesExporter::new(Vec::new(), StdoutFormat::Compact);
+///     // Export spans, then read the lines back
+///     let output = exporter.into_inner();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct JsonLinesExporter<W> {
+    writer: Mutex<W>,
+    format: StdoutFormat,
+    resource: Map<String, Json>,
+    is_shutdown: AtomicBool,
+}
+
+impl<W: Write + Debug + Send> JsonLinesExporter<W> {
+    /// Creates a new `JsonLinesExporter` writing to the provided writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The destination of the lines
+    /// * `format` - The line format, `StdoutFormat::Pretty` being written like `Json`
+    ///
+    /// # Returns
+    ///
+    /// A new `JsonLinesExporter` instance
+    pub fn new(writer: W, format: StdoutFormat) -> JsonLinesExporter<W> {
+        JsonLinesExporter {
+            writer: Mutex::new(writer),
+            format,
+            resource: Map::new(),
+            is_shutdown: AtomicBool::new(false),
+        }
+    }
+
+    /// Consumes the exporter, returning the writer.
+    ///
+    /// # Returns
+    ///
+    /// The writer, holding the lines written so far
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Writes a batch of spans, one line per span.
+    fn write(&self, batch: Vec<SpanData>) -> io::Result<()> {
+        if self.is_shutdown.load(Ordering::Relaxed) {
+            return Err(io::Error::other("the stdout exporter is shutdown"));
+        }
+
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|err| io::Error::other(err.to_string()))?;
+
+        let resource = match self.format {
+            StdoutFormat::Compact => None,
+            StdoutFormat::Pretty | StdoutFormat::Json => Some(&self.resource),
+        };
+
+        for span in batch {
+            let mut line = serde_json::to_vec(&json::span_to_json(&span, resource))?;
+            line.push(b'\n');
+            writer.write_all(&line)?;
+        }
+
+        writer.flush()
+    }
+}
+
+impl<W: Write + Debug + Send> SpanExporter for JsonLinesExporter<W> {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        self.write(batch)
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn shutdown_with_timeout(&mut self, _timeout: Duration) -> OTelSdkResult {
+        if self.is_shutdown.swap(true, Ordering::Relaxed) {
+            return Err(OTelSdkError::AlreadyShutdown);
+        }
+
+        self.writer
+            .lock()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?
+            .flush()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.resource = json::resource_to_json(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use opentelemetry::{KeyValue, trace::Tracer};
+
+    /// Ends a span per name and returns the finished spans.
+    fn spans(names: &[&'static str]) -> Vec<SpanData> {
+        let pipeline = test_utils::pipeline();
+        for name in names {
+            pipeline.tracer.in_span(*name, |_| {});
+        }
+        pipeline.spans()
+    }
+
+    /// Exports the spans and parses the written lines.
+    fn export(mut exporter: JsonLinesExporter<Vec<u8>>, batch: Vec<SpanData>) -> Vec<Json> {
+        test_utils::block_on(exporter.export(batch)).expect("export spans");
+        exporter
+            .shutdown_with_timeout(Duration::from_secs(1))
+            .expect("shutdown exporter");
+
+        String::from_utf8(exporter.into_inner())
+            .expect("UTF-8 output")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid JSON line"))
+            .collect()
+    }
+
+    fn resource() -> Resource {
+        Resource::builder_empty()
+            .with_attribute(KeyValue::new("service.name", "traces"))
+            .build()
+    }
+
+    #[test]
+    fn writes_a_json_line_per_span() {
+        let batch = spans(&["first", "second", "third"]);
+        let trace_ids: Vec<String> = batch
+            .iter()
+            .map(|span| span.span_context.trace_id().to_string())
+            .collect();
+
+        let lines = export(
+            JsonLinesExporter::new(Vec::new(), StdoutFormat::Json),
+            batch,
+        );
+
+        assert_eq!(lines.len(), 3);
+        for ((line, name), trace_id) in lines
+            .iter()
+            .zip(["first", "second", "third"])
+            .zip(trace_ids)
+        {
+            assert_eq!(line["name"], name);
+            assert_eq!(line["trace_id"], trace_id.as_str());
+        }
+    }
+
+    #[test]
+    fn writes_the_resource_unless_compact() {
+        let mut json = JsonLinesExporter::new(Vec::new(), StdoutFormat::Json);
+        json.set_resource(&resource());
+        let lines = export(json, spans(&["span"]));
+        assert_eq!(lines[0]["resource"]["service.name"], "traces");
+
+        let mut compact = JsonLinesExporter::new(Vec::new(), StdoutFormat::Compact);
+        compact.set_resource(&resource());
+        let lines = export(compact, spans(&["span"]));
+        assert!(lines[0].get("resource").is_none());
+    }
+
+    #[test]
+    fn fails_to_export_once_shutdown() {
+        let mut exporter = JsonLinesExporter::new(Vec::new(), StdoutFormat::Json);
+        exporter
+            .shutdown_with_timeout(Duration::from_secs(1))
+            .expect("shutdown exporter");
+
+        assert!(test_utils::block_on(exporter.export(spans(&["span"]))).is_err());
+        assert!(exporter.into_inner().is_empty());
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Environment trace context extractor.
+//!
+//! This module provides functionality to extract OpenTelemetry context from the
+//! `TRACEPARENT` and `TRACESTATE` environment variables, which CI systems and other
+//! orchestrators use to let child processes continue a trace.
+
+use crate::extractors::hashmap::HashMapExtractor;
+use opentelemetry::{Context, propagation::TextMapPropagator};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use std::{collections::HashMap, env};
+
+/// Environment variables holding the W3C trace context, with their header names.
+const TRACE_CONTEXT_VARS: [(&str, &str); 2] =
+    [("TRACEPARENT", "traceparent"), ("TRACESTATE", "tracestate")];
+
+/// Extracts the W3C trace context from the process environment.
+///
+/// The W3C propagator is always used, regardless of the globally installed propagator,
+/// since the environment variables follow the W3C format.
+///
+/// # Returns
+///
+/// A context holding the remote parent span context, or the current context if
+/// `TRACEPARENT` is unset or invalid
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::Tracer;
+/// use traces::extractors::env;
+///
+/// fn build_step() {
+///     let parent = env::context_from_env();
+///     let tracer = global::tracer("build");
+///     let span = tracer.start_with_context("build_step", &parent);
+/// }
+/// ```
+pub fn context_from_env() -> Context {
+    let vars = TRACE_CONTEXT_VARS
+        .iter()
+        .filter_map(|(var, header)| env::var(var).ok().map(|value| (header.to_string(), value)))
+        .collect::<HashMap<_, _>>();
+
+    TraceContextPropagator::new().extract(&HashMapExtractor::new(&vars))
+}
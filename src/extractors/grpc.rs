@@ -7,12 +7,17 @@
 //! This module provides functionality to extract OpenTelemetry context
 //! from gRPC metadata, allowing distributed tracing across gRPC service boundaries.
 
+use crate::{
+    extractors::{self, FromCarrier},
+    helpers::SpanGuard,
+};
 use opentelemetry::{
-    global::{self, BoxedSpan, BoxedTracer},
+    Context, KeyValue,
+    global::{BoxedSpan, BoxedTracer},
     propagation::Extractor,
-    trace::Tracer,
-    Context,
+    trace::{FutureExt, SpanKind, TraceContextExt, Tracer},
 };
+use std::future::Future;
 
 /// An OpenTelemetry context extractor for gRPC requests.
 ///
@@ -48,25 +53,103 @@ impl Extractor for GRPCExtractor<'_> {
     ///
     /// Option containing the value as a &str if found and convertible
     fn get(&self, key: &str) -> Option<&str> {
-        self.0.get(key).and_then(|metadata| metadata.to_str().ok())
+        self.0.carrier_get(key)
     }
 
-    /// Collect all the keys from the MetadataMap.
+    /// Collect the keys from the MetadataMap.
+    ///
+    /// Binary (`-bin`) keys are not returned, as their values can't be read with `get`.
     ///
     /// # Returns
     ///
-    /// A vector of all keys in the metadata map as string slices
+    /// A vector of the ASCII keys in the metadata map as string slices
     fn keys(&self) -> Vec<&str> {
-        self.0
-            .keys()
-            .map(|key| match key {
-                tonic::metadata::KeyRef::Ascii(v) => v.as_str(),
-                tonic::metadata::KeyRef::Binary(v) => v.as_str(),
+        self.0.carrier_keys()
+    }
+}
+
+impl FromCarrier for tonic::metadata::MetadataMap {
+    /// Get a value for a key from the MetadataMap.
+    ///
+    /// If the value can't be converted to &str, returns None.
+    fn carrier_get(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|metadata| metadata.to_str().ok())
+    }
+
+    /// Collect the ASCII keys from the MetadataMap.
+    ///
+    /// Binary keys, suffixed with `-bin`, are left out: their values are base64 encoded
+    /// bytes that `carrier_get` doesn't return, so propagators never see a key they can't read.
+    fn carrier_keys(&self) -> Vec<&str> {
+        self.keys()
+            .filter_map(|key| match key {
+                tonic::metadata::KeyRef::Ascii(v) => Some(v.as_str()),
+                tonic::metadata::KeyRef::Binary(_) => None,
             })
             .collect::<Vec<_>>()
     }
 }
 
+/// Converts a request extension into span attributes.
+///
+/// Implement this trait for the types stored in `tonic::Request` extensions (for example
+/// by a tower middleware) that should enrich the server span, such as the authenticated
+/// subject or the tenant.
+pub trait ExtensionAttributes: Send + Sync + 'static {
+    /// Returns the span attributes describing this extension.
+    fn attributes(&self) -> Vec<KeyValue>;
+}
+
+/// Reads span attributes from the extensions of a gRPC request.
+pub type ExtensionReader = fn(&tonic::Extensions) -> Vec<KeyValue>;
+
+/// Reads the attributes of the extension of type `E`, if present.
+///
+/// This function is meant to be used as an `ExtensionReader`, e.g. `read_extension::<Tenant>`.
+///
+/// # Arguments
+///
+/// * `extensions` - The extensions of the gRPC request
+///
+/// # Returns
+///
+/// The attributes of the extension, or an empty vector if the request doesn't carry it
+pub fn read_extension<E: ExtensionAttributes>(extensions: &tonic::Extensions) -> Vec<KeyValue> {
+    extensions
+        .get::<E>()
+        .map(|ext| ext.attributes())
+        .unwrap_or_default()
+}
+
+/// Extracts the trace context from gRPC metadata without starting a span.
+///
+/// The returned context holds the remote span context of the caller, if any, and its
+/// baggage, e.g. to start a span later with `helpers::child` or to decide whether to trace
+/// the request at all.
+///
+/// # Arguments
+///
+/// * `meta` - gRPC metadata containing potential trace context information
+///
+/// # Returns
+///
+/// The extracted context, with an invalid span context if the metadata carries none
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{extractors::grpc, helpers, provider};
+///
+/// fn say_hello(request: &tonic::Request<()>) {
+///     let parent = grpc::extract(request.metadata());
+///     let ctx = helpers::child(&provider::tracer(), &parent, SpanKind::Server, "SayHello");
+/// }
+/// ```
+pub fn extract(meta: &tonic::metadata::MetadataMap) -> Context {
+    extractors::extract(meta)
+}
+
 /// Creates a span from gRPC metadata using the provided tracer.
 ///
 /// This function extracts trace context from the gRPC metadata and creates a new span
@@ -80,8 +163,352 @@ impl Extractor for GRPCExtractor<'_> {
 /// # Returns
 ///
 /// A tuple containing the extracted context and a new span
+#[deprecated(note = "use `span_named` with the gRPC method name")]
 pub fn span(meta: &tonic::metadata::MetadataMap, tracer: &BoxedTracer) -> (Context, BoxedSpan) {
-    let ctx = global::get_text_map_propagator(|prop| prop.extract(&GRPCExtractor(meta)));
+    let ctx = extract(meta);
     let span = tracer.start_with_context("gRPC", &ctx);
+
+    (ctx, span)
+}
+
+/// Creates a server span named after the gRPC method from gRPC metadata.
+///
+/// This function extracts trace context from the gRPC metadata and creates a new `Server`
+/// span within that context, so each method is distinguishable in the trace viewer.
+///
+/// # Arguments
+///
+/// * `meta` - gRPC metadata containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `name` - The name of the span, conventionally `package.Service/Method`
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::global;
+/// use traces::extractors::grpc;
+///
+/// fn say_hello(request: &tonic::Request<()>) {
+///     let tracer = global::tracer("my_service");
+///     let (ctx, span) = grpc::span_named(request.metadata(), &tracer, "helloworld.Greeter/SayHello");
+/// }
+/// ```
+pub fn span_named(
+    meta: &tonic::metadata::MetadataMap,
+    tracer: &BoxedTracer,
+    name: &str,
+) -> (Context, BoxedSpan) {
+    let ctx = extract(meta);
+
+    let span = tracer
+        .span_builder(name.to_owned())
+        .with_kind(SpanKind::Server)
+        .start_with_context(tracer, &ctx);
+
+    (ctx, span)
+}
+
+/// Starts a server span from gRPC metadata and attaches it until the returned guard is dropped.
+///
+/// The span is a child of the context extracted from the metadata and is ended when the
+/// guard is dropped, after which the previous context is restored. The guard is not `Send`;
+/// use `instrument` in async handlers.
+///
+/// # Arguments
+///
+/// * `meta` - gRPC metadata containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `name` - The name of the span, conventionally `package.Service/Method`
+///
+/// # Returns
+///
+/// A `SpanGuard` whose context can be injected into downstream requests
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::global;
+/// use traces::{extractors::grpc, injectors};
+///
+/// fn say_hello(request: &tonic::Request<()>) {
+///     let tracer = global::tracer("my_service");
+///     let scope = grpc::scope(request.metadata(), &tracer, "helloworld.Greeter/SayHello");
+///
+///     let mut downstream = tonic::metadata::MetadataMap::new();
+///     injectors::grpc::inject(scope.context(), &mut downstream);
+///     // The span ends when `scope` goes out of scope
+/// }
+/// ```
+pub fn scope(meta: &tonic::metadata::MetadataMap, tracer: &BoxedTracer, name: &str) -> SpanGuard {
+    let (ctx, span) = span_named(meta, tracer, name);
+
+    SpanGuard::attach(ctx.with_span(span))
+}
+
+/// Runs a future inside a server span started from gRPC metadata.
+///
+/// This is the async counterpart of `scope`: the span is current whenever the future is
+/// polled, including after `.await` points, and is ended when the future completes. Inside
+/// the future, `Context::current()` holds the span for downstream injection.
+///
+/// # Arguments
+///
+/// * `meta` - gRPC metadata containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `name` - The name of the span, conventionally `package.Service/Method`
+/// * `fut` - The handler future
+///
+/// # Returns
+///
+/// A future running `fut` inside the span
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::global;
+/// use tonic::{Request, Response, Status};
+/// use traces::extractors::grpc;
+///
+/// async fn say_hello(request: Request<()>) -> Result<Response<()>, Status> {
+///     let tracer = global::tracer("my_service");
+///     grpc::instrument(request.metadata(), &tracer, "helloworld.Greeter/SayHello", async {
+///         Ok(Response::new(()))
+///     })
+///     .await
+/// }
+/// ```
+pub fn instrument<F: Future>(
+    meta: &tonic::metadata::MetadataMap,
+    tracer: &BoxedTracer,
+    name: &str,
+    fut: F,
+) -> impl Future<Output = F::Output> {
+    let (ctx, span) = span_named(meta, tracer, name);
+    let ctx = ctx.with_span(span);
+
+    async move {
+        let output = fut.with_context(ctx.clone()).await;
+        ctx.span().end();
+        output
+    }
+}
+
+/// Creates a span from a gRPC request, enriched with attributes read from its extensions.
+///
+/// This function extracts trace context from the request metadata and creates a new span
+/// within that context, carrying the attributes produced by each reader.
+///
+/// # Arguments
+///
+/// * `request` - gRPC request containing potential trace context information and extensions
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `readers` - Readers converting the request extensions into span attributes
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::{KeyValue, global};
+/// use traces::extractors::grpc::{self, ExtensionAttributes};
+///
+/// #[derive(Clone)]
+/// struct Tenant(String);
+///
+/// impl ExtensionAttributes for Tenant {
+///     fn attributes(&self) -> Vec<KeyValue> {
+///         vec![KeyValue::new("tenant.id", self.0.clone())]
+///     }
+/// }
+///
+/// fn handle(request: &tonic::Request<()>) {
+///     let tracer = global::tracer("my_service");
+///     let (ctx, span) =
+///         grpc::span_with_extensions(request, &tracer, &[grpc::read_extension::<Tenant>]);
+/// }
+/// ```
+pub fn span_with_extensions<T>(
+    request: &tonic::Request<T>,
+    tracer: &BoxedTracer,
+    readers: &[ExtensionReader],
+) -> (Context, BoxedSpan) {
+    let ctx = extract(request.metadata());
+
+    let attributes = readers
+        .iter()
+        .flat_map(|read| read(request.extensions()))
+        .collect::<Vec<_>>();
+
+    let span = tracer
+        .span_builder("gRPC")
+        .with_attributes(attributes)
+        .start_with_context(tracer, &ctx);
+
     (ctx, span)
 }
+
+/// Creates a span from a gRPC request, recording the address of the remote peer.
+///
+/// This function extracts trace context from the request metadata and creates a new span
+/// within that context, carrying the `client.address` and `client.port` attributes when the
+/// peer address is known by the transport.
+///
+/// # Arguments
+///
+/// * `request` - gRPC request containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+pub fn span_with_peer<T>(
+    request: &tonic::Request<T>,
+    tracer: &BoxedTracer,
+) -> (Context, BoxedSpan) {
+    let ctx = extract(request.metadata());
+
+    let attributes = request
+        .remote_addr()
+        .map(extractors::peer_attributes)
+        .unwrap_or_default();
+
+    let span = tracer
+        .span_builder("gRPC")
+        .with_attributes(attributes)
+        .start_with_context(tracer, &ctx);
+
+    (ctx, span)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use opentelemetry::trace::Span;
+    use tonic::metadata::{BinaryMetadataValue, MetadataMap, MetadataValue};
+
+    /// Returns metadata with an ASCII and a binary entry.
+    fn metadata() -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("x-tenant", MetadataValue::from_static("acme"));
+        metadata.insert_bin("x-tenant-bin", BinaryMetadataValue::from_bytes(b"acme"));
+        metadata
+    }
+
+    #[test]
+    fn reads_ascii_keys() {
+        let metadata = metadata();
+
+        assert_eq!(metadata.carrier_get("x-tenant"), Some("acme"));
+        assert_eq!(GRPCExtractor::new(&metadata).get("x-tenant"), Some("acme"));
+    }
+
+    #[test]
+    fn leaves_out_binary_keys() {
+        let metadata = metadata();
+
+        assert_eq!(metadata.carrier_get("x-tenant-bin"), None);
+        assert_eq!(metadata.carrier_keys(), vec!["x-tenant"]);
+        assert_eq!(GRPCExtractor::new(&metadata).keys(), vec!["x-tenant"]);
+    }
+
+    #[test]
+    fn names_the_server_span_after_the_method() {
+        let pipeline = test_utils::pipeline();
+
+        let (_ctx, mut span) = span_named(
+            &MetadataMap::new(),
+            &pipeline.tracer,
+            "helloworld.Greeter/SayHello",
+        );
+        span.end();
+
+        let exported = pipeline.spans().pop().expect("exported span");
+        assert_eq!(exported.name, "helloworld.Greeter/SayHello");
+        assert_eq!(exported.span_kind, SpanKind::Server);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn keeps_the_generic_name_of_the_deprecated_span() {
+        let pipeline = test_utils::pipeline();
+
+        let (_ctx, mut span) = span(&MetadataMap::new(), &pipeline.tracer);
+        span.end();
+
+        assert_eq!(pipeline.spans().pop().expect("exported span").name, "gRPC");
+    }
+
+    #[test]
+    fn ends_the_span_when_the_scope_drops() {
+        let pipeline = test_utils::pipeline();
+
+        let scope = scope(
+            &MetadataMap::new(),
+            &pipeline.tracer,
+            "helloworld.Greeter/SayHello",
+        );
+        let span_id = scope.context().span().span_context().span_id();
+        assert_eq!(Context::current().span().span_context().span_id(), span_id);
+        assert!(pipeline.spans().is_empty());
+
+        drop(scope);
+
+        let exported = pipeline.spans().pop().expect("exported span");
+        assert_eq!(exported.span_context.span_id(), span_id);
+        assert!(!Context::current().has_active_span());
+    }
+
+    #[test]
+    fn ends_the_span_when_the_instrumented_future_completes() {
+        let pipeline = test_utils::pipeline();
+
+        let span_id = test_utils::block_on(instrument(
+            &MetadataMap::new(),
+            &pipeline.tracer,
+            "helloworld.Greeter/SayHello",
+            async { Context::current().span().span_context().span_id() },
+        ));
+
+        let exported = pipeline.spans().pop().expect("exported span");
+        assert_eq!(exported.span_context.span_id(), span_id);
+    }
+
+    #[test]
+    fn extracts_the_remote_span_context_without_starting_a_span() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let mut metadata = MetadataMap::new();
+        metadata.insert(
+            "traceparent",
+            MetadataValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+        );
+
+        let ctx = extract(&metadata);
+
+        let span_context = ctx.span().span_context().clone();
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+        assert!(span_context.is_remote());
+        assert!(span_context.is_sampled());
+    }
+
+    #[test]
+    fn extracts_an_invalid_span_context_without_traceparent() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+
+        let ctx = extract(&MetadataMap::new());
+
+        assert!(!ctx.span().span_context().is_valid());
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! HashMap trace context extractor.
+//!
+//! This module provides functionality to extract OpenTelemetry context
+//! from plain string maps, such as HTTP headers or message bus headers.
+
+use crate::extractors::{self, FromCarrier};
+use opentelemetry::{
+    Context,
+    global::{BoxedSpan, BoxedTracer},
+    propagation::Extractor,
+};
+use std::collections::HashMap;
+
+/// An OpenTelemetry context extractor for string maps.
+///
+/// This struct implements the `Extractor` trait to allow extracting trace context
+/// from a `HashMap<String, String>`.
+pub struct HashMapExtractor<'a>(&'a HashMap<String, String>);
+
+impl<'a> HashMapExtractor<'a> {
+    /// Creates a new `HashMapExtractor` from a string map.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - Reference to a string map
+    ///
+    /// # Returns
+    ///
+    /// A new `HashMapExtractor` instance
+    pub fn new(m: &'a HashMap<String, String>) -> HashMapExtractor<'a> {
+        HashMapExtractor(m)
+    }
+}
+
+impl Extractor for HashMapExtractor<'_> {
+    /// Get a value for a key from the map.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up
+    ///
+    /// # Returns
+    ///
+    /// Option containing the value if found
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.carrier_get(key)
+    }
+
+    /// Collect all the keys from the map.
+    ///
+    /// # Returns
+    ///
+    /// A vector of all keys in the map as string slices
+    fn keys(&self) -> Vec<&str> {
+        self.0.carrier_keys()
+    }
+}
+
+impl FromCarrier for HashMap<String, String> {
+    /// Get a value for a key from the map.
+    ///
+    /// Falls back to a case-insensitive lookup, since HTTP header names are case-insensitive.
+    fn carrier_get(&self, key: &str) -> Option<&str> {
+        self.get(key)
+            .or_else(|| {
+                self.iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                    .map(|(_, v)| v)
+            })
+            .map(|v| v.as_str())
+    }
+
+    /// Collect all the keys from the map.
+    fn carrier_keys(&self) -> Vec<&str> {
+        self.keys().map(|k| k.as_str()).collect::<Vec<_>>()
+    }
+}
+
+/// Creates a span from a string map using the provided tracer.
+///
+/// This function extracts trace context from the map and creates a new span
+/// within that context.
+///
+/// # Arguments
+///
+/// * `map` - String map containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+pub fn span(map: &HashMap<String, String>, tracer: &BoxedTracer) -> (Context, BoxedSpan) {
+    extractors::span(map, tracer, "HTTP")
+}
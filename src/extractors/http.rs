@@ -0,0 +1,120 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! HTTP trace context extractor.
+//!
+//! This module provides functionality to extract OpenTelemetry context
+//! from the `http` crate `HeaderMap`, as used by hyper, axum and reqwest.
+
+use crate::extractors::{self, FromCarrier};
+use ::http::HeaderMap;
+use opentelemetry::{
+    Context,
+    global::{BoxedSpan, BoxedTracer},
+    propagation::Extractor,
+    trace::{SpanKind, Tracer},
+};
+
+/// An OpenTelemetry context extractor for HTTP requests.
+///
+/// This struct implements the `Extractor` trait to allow extracting trace context
+/// from HTTP headers.
+pub struct HTTPExtractor<'a>(&'a HeaderMap);
+
+impl<'a> HTTPExtractor<'a> {
+    /// Creates a new `HTTPExtractor` from HTTP headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Reference to the HTTP headers
+    ///
+    /// # Returns
+    ///
+    /// A new `HTTPExtractor` instance
+    pub fn new(h: &'a HeaderMap) -> HTTPExtractor<'a> {
+        HTTPExtractor(h)
+    }
+}
+
+impl Extractor for HTTPExtractor<'_> {
+    /// Get a value for a key from the headers.
+    ///
+    /// If the value is not visible ASCII, returns None.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header name to look up
+    ///
+    /// # Returns
+    ///
+    /// Option containing the value as a &str if found and convertible
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.carrier_get(key)
+    }
+
+    /// Collect the keys from the headers.
+    ///
+    /// # Returns
+    ///
+    /// A vector of the header names whose value is readable, as string slices
+    fn keys(&self) -> Vec<&str> {
+        self.0.carrier_keys()
+    }
+}
+
+impl FromCarrier for HeaderMap {
+    /// Get the first value for a key from the headers.
+    ///
+    /// Header names are case-insensitive. If the first value is not visible ASCII,
+    /// returns None.
+    fn carrier_get(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    /// Collect the names of the headers whose first value is visible ASCII.
+    fn carrier_keys(&self) -> Vec<&str> {
+        self.keys()
+            .filter(|name| self.carrier_get(name.as_str()).is_some())
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>()
+    }
+}
+
+/// Creates a server span from HTTP headers using the provided tracer.
+///
+/// This function extracts trace context from the headers and creates a new `Server`
+/// span within that context.
+///
+/// # Arguments
+///
+/// * `headers` - HTTP headers containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `name` - The name of the span, conventionally `{method} {route}`, e.g. `GET /users/{id}`
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use http::Request;
+/// use opentelemetry::global;
+/// use traces::extractors;
+///
+/// fn handle(request: &Request<()>) {
+///     let tracer = global::tracer("my_service");
+///     let (ctx, span) = extractors::http::span(request.headers(), &tracer, "GET /users/{id}");
+/// }
+/// ```
+pub fn span(headers: &HeaderMap, tracer: &BoxedTracer, name: &str) -> (Context, BoxedSpan) {
+    let ctx = extractors::extract(headers);
+
+    let span = tracer
+        .span_builder(name.to_owned())
+        .with_kind(SpanKind::Server)
+        .start_with_context(tracer, &ctx);
+
+    (ctx, span)
+}
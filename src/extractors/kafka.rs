@@ -0,0 +1,148 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Kafka trace context extractor.
+//!
+//! This module provides functionality to extract OpenTelemetry context
+//! from Kafka record headers, allowing distributed tracing across the message bus.
+
+use crate::extractors::{self, FromCarrier};
+use opentelemetry::{
+    Context,
+    global::{BoxedSpan, BoxedTracer},
+    propagation::Extractor,
+};
+use rdkafka::message::{BorrowedHeaders, Headers, OwnedHeaders};
+
+/// An OpenTelemetry context extractor for Kafka records.
+///
+/// This struct implements the `Extractor` trait to allow extracting trace context
+/// from the headers of a Kafka record, either `OwnedHeaders` or `BorrowedHeaders`.
+pub struct KafkaExtractor<'a, H>(&'a H);
+
+impl<'a, H: Headers> KafkaExtractor<'a, H> {
+    /// Creates a new `KafkaExtractor` from Kafka record headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Reference to the record headers
+    ///
+    /// # Returns
+    ///
+    /// A new `KafkaExtractor` instance
+    pub fn new(h: &'a H) -> KafkaExtractor<'a, H> {
+        KafkaExtractor(h)
+    }
+}
+
+impl<H: Headers> Extractor for KafkaExtractor<'_, H> {
+    /// Get a value for a key from the headers.
+    ///
+    /// If the value is missing or is not valid UTF-8, returns None.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header key to look up
+    ///
+    /// # Returns
+    ///
+    /// Option containing the value as a &str if found and valid UTF-8
+    fn get(&self, key: &str) -> Option<&str> {
+        header_get(self.0, key)
+    }
+
+    /// Collect the keys from the headers.
+    ///
+    /// Keys whose value is missing or is not valid UTF-8 are not returned.
+    ///
+    /// # Returns
+    ///
+    /// A vector of the readable keys in the headers as string slices
+    fn keys(&self) -> Vec<&str> {
+        header_keys(self.0)
+    }
+}
+
+impl FromCarrier for OwnedHeaders {
+    /// Get a value for a key from the headers.
+    ///
+    /// If the value is missing or is not valid UTF-8, returns None.
+    fn carrier_get(&self, key: &str) -> Option<&str> {
+        header_get(self, key)
+    }
+
+    /// Collect the keys whose value is valid UTF-8.
+    fn carrier_keys(&self) -> Vec<&str> {
+        header_keys(self)
+    }
+}
+
+impl FromCarrier for BorrowedHeaders {
+    /// Get a value for a key from the headers.
+    ///
+    /// If the value is missing or is not valid UTF-8, returns None.
+    fn carrier_get(&self, key: &str) -> Option<&str> {
+        header_get(self, key)
+    }
+
+    /// Collect the keys whose value is valid UTF-8.
+    fn carrier_keys(&self) -> Vec<&str> {
+        header_keys(self)
+    }
+}
+
+/// Returns the last UTF-8 value stored for a key, as Kafka headers may repeat keys.
+fn header_get<'a, H: Headers>(headers: &'a H, key: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .filter(|header| header.key == key)
+        .filter_map(|header| header.value)
+        .filter_map(|value| std::str::from_utf8(value).ok())
+        .last()
+}
+
+/// Returns the keys having a UTF-8 value.
+fn header_keys<H: Headers>(headers: &H) -> Vec<&str> {
+    headers
+        .iter()
+        .filter(|header| {
+            header
+                .value
+                .is_some_and(|value| std::str::from_utf8(value).is_ok())
+        })
+        .map(|header| header.key)
+        .collect::<Vec<_>>()
+}
+
+/// Creates a span from Kafka record headers using the provided tracer.
+///
+/// This function extracts trace context from the record headers and creates a new span
+/// within that context.
+///
+/// # Arguments
+///
+/// * `headers` - Record headers containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::global;
+/// use rdkafka::message::{BorrowedMessage, Message};
+/// use traces::extractors::kafka;
+///
+/// fn consume(message: &BorrowedMessage<'_>) {
+///     let tracer = global::tracer("my_service");
+///     if let Some(headers) = message.headers() {
+///         let (ctx, span) = kafka::span(headers, &tracer);
+///     }
+/// }
+/// ```
+pub fn span<H: Headers + FromCarrier>(headers: &H, tracer: &BoxedTracer) -> (Context, BoxedSpan) {
+    extractors::span(headers, tracer, "Kafka")
+}
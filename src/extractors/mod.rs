@@ -6,6 +6,121 @@
 //!
 //! This module provides extractors for retrieving trace context information
 //! from various transport protocols and formats.
+//!
+//! Any transport can be supported by implementing `FromCarrier` for its header
+//! container, which provides the `extract` and `span` helpers for free.
+
+use opentelemetry::{
+    Context, KeyValue,
+    global::{self, BoxedSpan, BoxedTracer},
+    propagation::Extractor,
+    trace::Tracer,
+};
+use std::net::SocketAddr;
 
+pub mod env;
 #[cfg(feature = "otlp")]
 pub mod grpc;
+pub mod hashmap;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+/// A transport carrier from which trace context can be extracted.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use traces::extractors::FromCarrier;
+///
+/// struct Headers(BTreeMap<String, String>);
+///
+/// impl FromCarrier for Headers {
+///     fn carrier_get(&self, key: &str) -> Option<&str> {
+///         self.0.get(key).map(|v| v.as_str())
+///     }
+///
+///     fn carrier_keys(&self) -> Vec<&str> {
+///         self.0.keys().map(|k| k.as_str()).collect()
+///     }
+/// }
+/// ```
+pub trait FromCarrier {
+    /// Returns the value stored for a key, or `None` if absent or not representable as text.
+    fn carrier_get(&self, key: &str) -> Option<&str>;
+
+    /// Returns all the keys stored in the carrier.
+    fn carrier_keys(&self) -> Vec<&str>;
+}
+
+/// An OpenTelemetry `Extractor` over any `FromCarrier`.
+struct CarrierExtractor<'a, C: ?Sized>(&'a C);
+
+impl<C: FromCarrier + ?Sized> Extractor for CarrierExtractor<'_, C> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.carrier_get(key)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.carrier_keys()
+    }
+}
+
+/// Extracts the trace context from a carrier using the global propagator.
+///
+/// # Arguments
+///
+/// * `carrier` - The carrier containing potential trace context information
+///
+/// # Returns
+///
+/// The extracted context
+pub fn extract<C: FromCarrier + ?Sized>(carrier: &C) -> Context {
+    global::get_text_map_propagator(|prop| prop.extract(&CarrierExtractor(carrier)))
+}
+
+/// Creates a span from a carrier using the provided tracer.
+///
+/// This function extracts trace context from the carrier and creates a new span
+/// within that context.
+///
+/// # Arguments
+///
+/// * `carrier` - The carrier containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `name` - The name of the span
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+pub fn span<C: FromCarrier + ?Sized>(
+    carrier: &C,
+    tracer: &BoxedTracer,
+    name: &str,
+) -> (Context, BoxedSpan) {
+    let ctx = extract(carrier);
+    let span = tracer.start_with_context(name.to_owned(), &ctx);
+    (ctx, span)
+}
+
+/// Builds the `client.address` and `client.port` attributes of a remote peer.
+///
+/// These attributes follow the OpenTelemetry semantic conventions for server spans.
+///
+/// # Arguments
+///
+/// * `peer` - The socket address of the remote peer
+///
+/// # Returns
+///
+/// The peer attributes
+pub fn peer_attributes(peer: SocketAddr) -> Vec<KeyValue> {
+    vec![
+        KeyValue::new("client.address", peer.ip().to_string()),
+        KeyValue::new("client.port", i64::from(peer.port())),
+    ]
+}
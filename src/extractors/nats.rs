@@ -0,0 +1,127 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! NATS trace context extractor.
+//!
+//! This module provides functionality to extract OpenTelemetry context
+//! from NATS message headers, allowing distributed tracing across subjects.
+
+use crate::extractors::{self, FromCarrier};
+use async_nats::HeaderMap;
+use opentelemetry::{
+    Context,
+    global::{BoxedSpan, BoxedTracer},
+    propagation::Extractor,
+    trace::{SpanKind, Tracer},
+};
+
+/// An OpenTelemetry context extractor for NATS messages.
+///
+/// This struct implements the `Extractor` trait to allow extracting trace context
+/// from the headers of a NATS message.
+pub struct NatsExtractor<'a>(&'a HeaderMap);
+
+impl<'a> NatsExtractor<'a> {
+    /// Creates a new `NatsExtractor` from NATS message headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Reference to the message headers
+    ///
+    /// # Returns
+    ///
+    /// A new `NatsExtractor` instance
+    pub fn new(h: &'a HeaderMap) -> NatsExtractor<'a> {
+        NatsExtractor(h)
+    }
+}
+
+impl Extractor for NatsExtractor<'_> {
+    /// Get a value for a key from the headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header name to look up
+    ///
+    /// # Returns
+    ///
+    /// Option containing the first value of the header as a &str if found
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.carrier_get(key)
+    }
+
+    /// Collect the keys from the headers.
+    ///
+    /// # Returns
+    ///
+    /// A vector of the header names holding a value, as string slices
+    fn keys(&self) -> Vec<&str> {
+        self.0.carrier_keys()
+    }
+}
+
+impl FromCarrier for HeaderMap {
+    /// Get the first value for a key from the headers.
+    ///
+    /// NATS header names are case-sensitive, so when no header matches the key exactly, the
+    /// first header matching it case-insensitively is used, e.g. `Traceparent` set by another
+    /// client. NATS header values are always valid UTF-8, so every value is readable.
+    fn carrier_get(&self, key: &str) -> Option<&str> {
+        self.get(key)
+            .or_else(|| {
+                self.iter()
+                    .find(|(name, _)| name.as_str().eq_ignore_ascii_case(key))
+                    .and_then(|(_, values)| values.first())
+            })
+            .map(|value| value.as_str())
+    }
+
+    /// Collect the names of the headers holding at least one value.
+    fn carrier_keys(&self) -> Vec<&str> {
+        self.iter()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+    }
+}
+
+/// Creates a consumer span from NATS message headers using the provided tracer.
+///
+/// This function extracts trace context from the headers and creates a new `Consumer`
+/// span within that context.
+///
+/// # Arguments
+///
+/// * `headers` - NATS message headers containing potential trace context information
+/// * `tracer` - OpenTelemetry tracer to use for creating the span
+/// * `name` - The name of the span, conventionally `{subject} process`
+///
+/// # Returns
+///
+/// A tuple containing the extracted context and a new span
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_nats::Message;
+/// use opentelemetry::global;
+/// use traces::extractors;
+///
+/// fn handle(message: &Message) {
+///     let tracer = global::tracer("my_service");
+///     if let Some(headers) = &message.headers {
+///         let (ctx, span) = extractors::nats::span(headers, &tracer, "orders.created process");
+///     }
+/// }
+/// ```
+pub fn span(headers: &HeaderMap, tracer: &BoxedTracer, name: &str) -> (Context, BoxedSpan) {
+    let ctx = extractors::extract(headers);
+
+    let span = tracer
+        .span_builder(name.to_owned())
+        .with_kind(SpanKind::Consumer)
+        .start_with_context(tracer, &ctx);
+
+    (ctx, span)
+}
@@ -8,15 +8,28 @@
 //! spans, and trace/span identifiers. These helpers make it easier to create
 //! and inspect trace contexts throughout the application.
 
+use crate::{errors::TracesError, provider};
 use opentelemetry::{
-    Context,
-    global::BoxedTracer,
-    trace::{SpanKind, TraceContextExt, Tracer},
+    Context, ContextGuard, KeyValue, Value,
+    baggage::BaggageExt,
+    global::{self, BoxedTracer},
+    trace::{
+        FutureExt, Link, Span, SpanContext, SpanId, SpanKind, Status, TraceContextExt, TraceFlags,
+        TraceId, TraceState, Tracer,
+    },
+};
+use opentelemetry_sdk::trace::{IdGenerator, RandomIdGenerator};
+use std::{
+    borrow::Cow,
+    error::Error,
+    time::{Instant, SystemTime},
 };
-use std::borrow::Cow;
 
 /// Creates a new span context with the specified kind and name.
 ///
+/// The default attributes configured for the span kind, see
+/// `TracesConfigs::span_kind_attributes`, are set on the span when it starts.
+///
 /// # Arguments
 ///
 /// * `tracer` - The OpenTelemetry tracer to use
@@ -49,6 +62,439 @@ pub fn ctx(tracer: &BoxedTracer, kind: SpanKind, name: &str) -> Context {
     Context::current_with_span(span)
 }
 
+/// Creates a new span context as a child of an explicit parent context.
+///
+/// Unlike `ctx`, which parents the span under `Context::current()`, the span is a child
+/// of the span held by `parent`, e.g. a context extracted from request metadata that was
+/// not attached. The returned context also keeps the baggage and other values of `parent`.
+///
+/// # Arguments
+///
+/// * `tracer` - The OpenTelemetry tracer to use
+/// * `parent` - The context holding the parent span
+/// * `kind` - The kind of span to create (Server, Client, etc.)
+/// * `name` - The name of the span
+///
+/// # Returns
+///
+/// A new Context containing the created span
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use std::collections::HashMap;
+/// use traces::{extractors, helpers};
+///
+/// fn handle_message(headers: &HashMap<String, String>) {
+///     let tracer = global::tracer("my_service");
+///     let parent = extractors::extract(headers);
+///     let ctx = helpers::child(&tracer, &parent, SpanKind::Consumer, "handle_message");
+/// }
+/// ```
+pub fn child(tracer: &BoxedTracer, parent: &Context, kind: SpanKind, name: &str) -> Context {
+    let span = tracer
+        .span_builder(Cow::from(name.to_owned()))
+        .with_kind(kind)
+        .start_with_context(tracer, parent);
+
+    parent.with_span(span)
+}
+
+/// A guard that keeps a span's context attached and ends the span on drop.
+///
+/// The context is attached as the current context on creation, so spans created
+/// while the guard is alive become its children. On drop, the span is ended first
+/// and the previous context is then restored.
+///
+/// Nested guards must be dropped in the reverse order of their creation, which is
+/// the natural order for guards bound to lexical scopes. The guard is not `Send`
+/// and must not be held across `.await` points; use it for synchronous code paths.
+pub struct SpanGuard {
+    ctx: Context,
+    _attached: ContextGuard,
+}
+
+impl SpanGuard {
+    /// Attaches a context holding a span until the returned guard is dropped.
+    pub(crate) fn attach(ctx: Context) -> SpanGuard {
+        let attached = ctx.clone().attach();
+
+        SpanGuard {
+            ctx,
+            _attached: attached,
+        }
+    }
+
+    /// Returns the context holding the guarded span, e.g. to inject it into outgoing requests.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        self.ctx.span().end();
+    }
+}
+
+/// Creates a new span and attaches its context until the returned guard is dropped.
+///
+/// # Arguments
+///
+/// * `tracer` - The OpenTelemetry tracer to use
+/// * `kind` - The kind of span to create (Server, Client, etc.)
+/// * `name` - The name of the span
+///
+/// # Returns
+///
+/// A `SpanGuard` ending the span when dropped
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn process_request() {
+///     let tracer = global::tracer("my_service");
+///     let guard = helpers::scoped(&tracer, SpanKind::Server, "process_request");
+///     {
+///         let _child = helpers::scoped(&tracer, SpanKind::Internal, "load_user");
+///         // The child span ends here
+///     }
+///     // The parent span ends when `guard` goes out of scope
+/// }
+/// ```
+pub fn scoped(tracer: &BoxedTracer, kind: SpanKind, name: &str) -> SpanGuard {
+    SpanGuard::attach(ctx(tracer, kind, name))
+}
+
+/// Context flag marking a scope in which no span is sampled.
+#[derive(Debug, Clone, Copy)]
+struct SuppressTracing;
+
+/// Runs a closure with tracing suppressed.
+///
+/// The closure runs under a context flagged as suppressed, so the samplers installed by
+/// this crate drop every span started within it, including spans created by third-party
+/// libraries. The previous context is restored once the closure returns. The flag follows
+/// the current context, so it does not apply to work moved to other threads or tasks.
+///
+/// # Arguments
+///
+/// * `f` - The closure to run without tracing
+///
+/// # Returns
+///
+/// The value returned by the closure
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::helpers;
+///
+/// fn refresh_cache() {
+///     // Spans of the chatty client are not recorded
+///     let _entries = helpers::suppress(|| std::fs::read_dir("/var/cache/app").map(|d| d.count()));
+/// }
+/// ```
+pub fn suppress<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = Context::current_with_value(SuppressTracing).attach();
+
+    f()
+}
+
+/// Returns whether tracing is suppressed in a Context, see `suppress`.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context to inspect
+pub fn is_suppressed(ctx: &Context) -> bool {
+    ctx.get::<SuppressTracing>().is_some()
+}
+
+/// Generates a fresh W3C `traceparent` header value.
+///
+/// The trace and span IDs come from the id generator of the installed tracer provider,
+/// falling back to random IDs when none is installed, and the sampled flag is set. The
+/// span used to generate the IDs is started in a suppressed scope, so it is never exported.
+/// This is meant for test harnesses and load-testing tools simulating upstream services.
+///
+/// # Returns
+///
+/// A `traceparent` value, e.g. `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use traces::helpers;
+///
+/// fn simulated_request() -> HashMap<String, String> {
+///     HashMap::from([("traceparent".to_owned(), helpers::generate_traceparent())])
+/// }
+/// ```
+pub fn generate_traceparent() -> String {
+    let tracer = global::tracer_with_scope(provider::scope("traces"));
+    let parent = Context::new().with_value(SuppressTracing);
+    let span = tracer.start_with_context("traceparent", &parent);
+
+    let mut trace_id = span.span_context().trace_id();
+    let mut span_id = span.span_context().span_id();
+    if trace_id == TraceId::INVALID || span_id == SpanId::INVALID {
+        let id_generator = RandomIdGenerator::default();
+        trace_id = id_generator.new_trace_id();
+        span_id = id_generator.new_span_id();
+    }
+
+    format_traceparent(&SpanContext::new(
+        trace_id,
+        span_id,
+        TraceFlags::SAMPLED,
+        true,
+        TraceState::default(),
+    ))
+}
+
+/// Formats a span context as a W3C `traceparent` value.
+fn format_traceparent(span_context: &SpanContext) -> String {
+    format!(
+        "00-{}-{}-{:02x}",
+        span_context.trace_id(),
+        span_context.span_id(),
+        span_context.trace_flags().to_u8()
+    )
+}
+
+/// Runs a future inside a Context.
+///
+/// The context is attached each time the future is polled and detached when it yields,
+/// so spans created within the future, including after `.await` points and on whichever
+/// thread polls it, become children of the context's span. Unlike `scoped`, this is safe
+/// to use with async code.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context the future runs in, e.g. the extracted server span
+/// * `fut` - The future to run
+///
+/// # Returns
+///
+/// A future running `fut` inside `ctx`
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// async fn call_downstream() {
+///     let tracer = global::tracer("my_service");
+///     // Child of the `handle` span, even though it is created after an `.await`
+///     let _ctx = helpers::ctx(&tracer, SpanKind::Client, "call_downstream");
+/// }
+///
+/// async fn handle() {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "handle");
+///     helpers::instrument(ctx, async {
+///         std::future::ready(()).await;
+///         call_downstream().await;
+///     })
+///     .await;
+/// }
+/// ```
+pub fn instrument<F: Future>(ctx: Context, fut: F) -> impl Future<Output = F::Output> {
+    fut.with_context(ctx)
+}
+
+/// Creates a new span context with the specified kind, name and start time.
+///
+/// This is useful to reconstruct the timing of past operations, e.g. when replaying
+/// historical events. Pair it with `end_at` to also set the end time.
+///
+/// # Arguments
+///
+/// * `tracer` - The OpenTelemetry tracer to use
+/// * `kind` - The kind of span to create (Server, Client, etc.)
+/// * `name` - The name of the span
+/// * `start` - The start time of the span
+///
+/// # Returns
+///
+/// A new Context containing the created span
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use std::time::{Duration, SystemTime};
+/// use traces::helpers;
+///
+/// fn replay_event() {
+///     let tracer = global::tracer("my_service");
+///     let start = SystemTime::now() - Duration::from_secs(60);
+///     let ctx = helpers::ctx_at(&tracer, SpanKind::Internal, "replayed_event", start);
+///     helpers::end_at(&ctx, start + Duration::from_millis(250));
+/// }
+/// ```
+pub fn ctx_at(tracer: &BoxedTracer, kind: SpanKind, name: &str, start: SystemTime) -> Context {
+    let span = tracer
+        .span_builder(Cow::from(name.to_owned()))
+        .with_kind(kind)
+        .with_start_time(start)
+        .start(tracer);
+
+    Context::current_with_span(span)
+}
+
+/// Creates a new span context with the specified kind, name and links.
+///
+/// This is useful for fan-in operations, e.g. a consumer processing a batch of messages
+/// that each carry their own trace context. Links beyond the `max_links_per_span` limit
+/// configured on the provider are dropped.
+///
+/// # Arguments
+///
+/// * `tracer` - The OpenTelemetry tracer to use
+/// * `kind` - The kind of span to create (Server, Client, etc.)
+/// * `name` - The name of the span
+/// * `links` - The links to the related spans, see `link`
+///
+/// # Returns
+///
+/// A new Context containing the created span
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use std::collections::HashMap;
+/// use traces::{extractors, helpers};
+///
+/// fn process_batch(messages: Vec<HashMap<String, String>>) {
+///     let tracer = global::tracer("my_service");
+///     let links = messages
+///         .iter()
+///         .filter_map(|headers| helpers::link(&extractors::extract(headers)))
+///         .collect();
+///     let ctx = helpers::ctx_with_links(&tracer, SpanKind::Consumer, "process_batch", links);
+/// }
+/// ```
+pub fn ctx_with_links(
+    tracer: &BoxedTracer,
+    kind: SpanKind,
+    name: &str,
+    links: Vec<Link>,
+) -> Context {
+    let span = tracer
+        .span_builder(Cow::from(name.to_owned()))
+        .with_kind(kind)
+        .with_links(links)
+        .start(tracer);
+
+    Context::current_with_span(span)
+}
+
+/// Creates a link to the span of a Context, e.g. a context extracted from a message.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span to link to
+///
+/// # Returns
+///
+/// The link, or `None` if the Context holds no valid span context
+pub fn link(ctx: &Context) -> Option<Link> {
+    let span_context = ctx.span().span_context().clone();
+
+    if span_context.is_valid() {
+        return Some(Link::with_context(span_context));
+    }
+
+    None
+}
+
+/// Ends the span of a Context at the specified time.
+///
+/// Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `end` - The end time of the span
+pub fn end_at(ctx: &Context, end: SystemTime) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.end_with_timestamp(end);
+    }
+}
+
+/// Creates a child span of `parent` representing a retry attempt.
+///
+/// The span is named `retry` and carries the `retry.attempt` and `retry.max` attributes,
+/// so each attempt of a retried operation shows up in the trace. End it once the attempt
+/// completes, e.g. with `record_error` or `set_ok` before ending.
+///
+/// # Arguments
+///
+/// * `tracer` - The OpenTelemetry tracer to use
+/// * `parent` - The Context holding the span of the retried operation
+/// * `attempt` - The attempt number, starting at 1
+/// * `max` - The maximum number of attempts
+///
+/// # Returns
+///
+/// A new Context containing the retry span
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::{SpanKind, TraceContextExt};
+/// use traces::helpers;
+///
+/// fn fetch_with_retries() {
+///     let tracer = global::tracer("my_service");
+///     let parent = helpers::ctx(&tracer, SpanKind::Client, "fetch");
+///     for attempt in 1..=3 {
+///         let ctx = helpers::retry_span(&tracer, &parent, attempt, 3);
+///         let result = std::fs::read("config.toml");
+///         match &result {
+///             Ok(_) => helpers::set_ok(&ctx),
+///             Err(err) => helpers::record_error(&ctx, err),
+///         }
+///         ctx.span().end();
+///         if result.is_ok() {
+///             break;
+///         }
+///     }
+///     parent.span().end();
+/// }
+/// ```
+pub fn retry_span(tracer: &BoxedTracer, parent: &Context, attempt: u32, max: u32) -> Context {
+    let span = tracer
+        .span_builder("retry")
+        .with_kind(SpanKind::Internal)
+        .with_attributes(vec![
+            KeyValue::new("retry.attempt", i64::from(attempt)),
+            KeyValue::new("retry.max", i64::from(max)),
+        ])
+        .start_with_context(tracer, parent);
+
+    parent.with_span(span)
+}
+
 /// Extracts the trace ID from a Context.
 ///
 /// # Arguments
@@ -85,30 +531,122 @@ pub fn trace_id(ctx: &Context) -> String {
     String::new()
 }
 
-/// Extracts the span ID from a Context.
+/// Extracts the trace ID of the currently active span.
 ///
-/// Extracts the span ID from a Context.
+/// This reads `Context::current()`, i.e. the Context attached by `scoped`, `instrument` or
+/// `Context::attach`, so log statements can include the trace ID without a Context at hand.
+///
+/// # Returns
+///
+/// A string representation of the trace ID, or an empty string if no recording span is active
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{helpers, provider};
+///
+/// fn handle() {
+///     let _guard = helpers::scoped(&provider::tracer(), SpanKind::Server, "handle");
+///     tracing::info!(trace_id = helpers::current_trace_id(), "handling request");
+/// }
+/// ```
+pub fn current_trace_id() -> String {
+    trace_id(&Context::current())
+}
+
+/// Formats in which a trace ID can be rendered for log correlation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraceIdFormat {
+    /// 32 lowercase hex characters, the W3C format, e.g. `4bf92f3577b34da6a3ce929d0e0e4736`.
+    #[default]
+    Hex,
+    /// Dashed UUID layout of the hex format, e.g. `4bf92f35-77b3-4da6-a3ce-929d0e0e4736`.
+    Uuid,
+    /// Decimal representation of the lower 64 bits, as indexed by Datadog.
+    Decimal,
+}
+
+/// Extracts the trace ID from a Context in the specified format.
 ///
 /// # Arguments
 ///
 /// * `ctx` - The Context containing the span
+/// * `format` - The format of the returned trace ID
 ///
 /// # Returns
 ///
-/// A string representation of the span ID, or an empty string if the span is not recording
+/// The formatted trace ID, or an empty string if the span is not recording
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use opentelemetry::global;
 /// use opentelemetry::trace::SpanKind;
-/// use traces::helpers;
+/// use traces::helpers::{self, TraceIdFormat};
 ///
-/// fn log_span_info() {
+/// fn log_dd_trace_id() {
 ///     let tracer = global::tracer("my_service");
 ///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "my_operation");
-///     let span_id = helpers::span_id(&ctx);
-///     println!("Current span ID: {}", span_id);
+///     let trace_id = helpers::trace_id_as(&ctx, TraceIdFormat::Decimal);
+///     println!("dd.trace_id={}", trace_id);
+/// }
+/// ```
+pub fn trace_id_as(ctx: &Context, format: TraceIdFormat) -> String {
+    let span = ctx.span();
+
+    if !span.is_recording() {
+        return String::new();
+    }
+
+    let trace_id = span.span_context().trace_id();
+
+    match format {
+        TraceIdFormat::Hex => trace_id.to_string(),
+        TraceIdFormat::Uuid => {
+            let hex = trace_id.to_string();
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..]
+            )
+        }
+        TraceIdFormat::Decimal => {
+            let bytes = trace_id.to_bytes();
+            let mut lower = [0u8; 8];
+            lower.copy_from_slice(&bytes[8..]);
+            u64::from_be_bytes(lower).to_string()
+        }
+    }
+}
+
+/// Extracts the span ID from a Context.
+///
+/// Extracts the span ID from a Context.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+///
+/// # Returns
+///
+/// A string representation of the span ID, or an empty string if the span is not recording
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn log_span_info() {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "my_operation");
+///     let span_id = helpers::span_id(&ctx);
+///     println!("Current span ID: {}", span_id);
 /// }
 /// ```
 pub fn span_id(ctx: &Context) -> String {
@@ -122,3 +660,1172 @@ pub fn span_id(ctx: &Context) -> String {
 
     String::new()
 }
+
+/// Extracts the span ID of the currently active span.
+///
+/// This reads `Context::current()`, like `current_trace_id`.
+///
+/// # Returns
+///
+/// A string representation of the span ID, or an empty string if no recording span is active
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{helpers, provider};
+///
+/// fn handle() {
+///     let _guard = helpers::scoped(&provider::tracer(), SpanKind::Server, "handle");
+///     tracing::info!(span_id = helpers::current_span_id(), "handling request");
+/// }
+/// ```
+pub fn current_span_id() -> String {
+    span_id(&Context::current())
+}
+
+/// Builds a Context continuing a trace from raw trace and span IDs.
+///
+/// This is meant for stitching traces manually when the IDs are received out-of-band, e.g.
+/// from a log line or a custom header: the returned Context holds a remote span context,
+/// so spans started from it become children of the span identified by `span_id`.
+///
+/// # Arguments
+///
+/// * `trace_id` - The trace ID, 32 hex characters
+/// * `span_id` - The parent span ID, 16 hex characters
+/// * `sampled` - Whether the parent span was sampled
+///
+/// # Returns
+///
+/// * `Ok(Context)` holding the remote span context
+/// * `Err(TracesError::ConversionError)` if an ID is not valid hex, has the wrong length
+///   or is all zeros
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{helpers, provider};
+///
+/// fn resume() {
+///     let parent = helpers::context_from_ids(
+///         "4bf92f3577b34da6a3ce929d0e0e4736",
+///         "00f067aa0ba902b7",
+///         true,
+///     )
+///     .expect("Invalid trace IDs");
+///     let ctx = helpers::child(&provider::tracer(), &parent, SpanKind::Consumer, "resume");
+/// }
+/// ```
+pub fn context_from_ids(
+    trace_id: &str,
+    span_id: &str,
+    sampled: bool,
+) -> Result<Context, TracesError> {
+    let (trace_id, span_id) = (trace_id.trim(), span_id.trim());
+    let is_hex =
+        |id: &str, len: usize| id.len() == len && id.bytes().all(|b| b.is_ascii_hexdigit());
+    if !is_hex(trace_id, 32) || !is_hex(span_id, 16) {
+        return Err(TracesError::ConversionError);
+    }
+
+    let trace_id = TraceId::from_hex(trace_id).map_err(|_| TracesError::ConversionError)?;
+    let span_id = SpanId::from_hex(span_id).map_err(|_| TracesError::ConversionError)?;
+    if trace_id == TraceId::INVALID || span_id == SpanId::INVALID {
+        return Err(TracesError::ConversionError);
+    }
+
+    let trace_flags = if sampled {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+
+    Ok(Context::new().with_remote_span_context(SpanContext::new(
+        trace_id,
+        span_id,
+        trace_flags,
+        true,
+        TraceState::default(),
+    )))
+}
+
+/// Formats the span of a Context as a W3C `traceparent` value.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+///
+/// # Returns
+///
+/// The `traceparent` value, e.g. `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`,
+/// or an empty string if the span is not recording
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn log_traceparent() {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "my_operation");
+///     println!("traceparent={}", helpers::traceparent(&ctx));
+/// }
+/// ```
+pub fn traceparent(ctx: &Context) -> String {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        return format_traceparent(span.span_context());
+    }
+
+    String::new()
+}
+
+/// Extracts the trace flags from a Context.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+///
+/// # Returns
+///
+/// The trace flags, whose lowest bit is the sampled flag, or 0 if the span is not recording
+pub fn trace_flags(ctx: &Context) -> u8 {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        return span.span_context().trace_flags().to_u8();
+    }
+
+    0
+}
+
+/// Returns whether the span of a Context is sampled, i.e. will be exported.
+///
+/// Spans recorded but not sampled, see `SamplerKind::RecordOnly`, return `false`, so callers
+/// can skip computing costly attributes that would never be exported.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+///
+/// # Returns
+///
+/// `true` if the span is recording and sampled, `false` otherwise
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::Context;
+/// use traces::helpers;
+///
+/// fn annotate(ctx: &Context, rows: &[Vec<u8>]) {
+///     if helpers::is_sampled(ctx) {
+///         let bytes: usize = rows.iter().map(Vec::len).sum();
+///         helpers::set_attribute(ctx, "db.response.bytes", bytes as i64);
+///     }
+/// }
+/// ```
+pub fn is_sampled(ctx: &Context) -> bool {
+    let span = ctx.span();
+
+    span.is_recording() && span.span_context().is_sampled()
+}
+
+/// The sampling state of a span, as seen from its Context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SamplingState {
+    /// The span is recorded and sampled, so it will be exported.
+    RecordAndSample,
+    /// The span is recorded but not sampled, see `SamplerKind::RecordOnly`, so it is seen
+    /// by the span processors but never exported.
+    RecordOnly,
+    /// The sampler dropped the span: it is neither recorded nor sampled. Root spans created
+    /// while tracing is disabled are dropped too, by the sampler of the no-op provider, as are
+    /// the spans created within `suppress`.
+    Drop,
+    /// The Context holds no span created by this process, e.g. no active span, or only the
+    /// remote span context of an extracted parent, so no decision was taken locally.
+    NotRecording,
+}
+
+/// Returns the sampling state of the span of a Context.
+///
+/// This is a diagnostic helper to understand why a span is not exported, it should not
+/// drive any logic; use `is_sampled` to skip costly instrumentation instead.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+///
+/// # Returns
+///
+/// The `SamplingState` derived from the span context and recording state
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::Context;
+/// use traces::helpers::{self, SamplingState};
+///
+/// fn debug_sampling(ctx: &Context) {
+///     if helpers::sampling_decision(ctx) != SamplingState::RecordAndSample {
+///         tracing::debug!(state = ?helpers::sampling_decision(ctx), "span not exported");
+///     }
+/// }
+/// ```
+pub fn sampling_decision(ctx: &Context) -> SamplingState {
+    let span = ctx.span();
+    let span_context = span.span_context();
+
+    match (span.is_recording(), span_context.is_sampled()) {
+        (true, true) => SamplingState::RecordAndSample,
+        (true, false) => SamplingState::RecordOnly,
+        (false, false) if span_context.is_valid() && !span_context.is_remote() => {
+            SamplingState::Drop
+        }
+        (false, _) => SamplingState::NotRecording,
+    }
+}
+
+/// Records an error on the span of a Context and marks the span as failed.
+///
+/// The error is recorded as an exception event and the span status is set to `Error`
+/// with the error's `Display` as description. Each error of the `source()` chain is
+/// also recorded as an exception event. Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `err` - The error to record
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn handle() {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "handle");
+///     match std::fs::read("config.toml") {
+///         Ok(_) => helpers::set_ok(&ctx),
+///         Err(err) => helpers::record_error(&ctx, &err),
+///     }
+/// }
+/// ```
+pub fn record_error(ctx: &Context, err: &dyn Error) {
+    let span = ctx.span();
+
+    if !span.is_recording() {
+        return;
+    }
+
+    span.record_error(err);
+
+    let mut source = err.source();
+    while let Some(cause) = source {
+        span.add_event(
+            "exception",
+            vec![KeyValue::new("exception.message", cause.to_string())],
+        );
+        source = cause.source();
+    }
+
+    span.set_status(Status::error(err.to_string()));
+}
+
+/// Marks the span of a Context as successful.
+///
+/// Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+pub fn set_ok(ctx: &Context) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.set_status(Status::Ok);
+    }
+}
+
+/// Records the HTTP response status code on the span of a Context.
+///
+/// Sets the `http.response.status_code` attribute and maps the code to the span status
+/// following the HTTP semantic conventions: `5xx` codes are errors for both kinds, `4xx`
+/// codes are errors for client spans only, as they are a valid answer of a server. Other
+/// codes leave the status unset. Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `status_code` - The HTTP response status code
+/// * `kind` - The kind of the span, `SpanKind::Server` or `SpanKind::Client`
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn handle() {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "GET /users");
+///     // Leaves the status unset, a 404 is not a server error
+///     helpers::set_http_status(&ctx, 404, SpanKind::Server);
+/// }
+/// ```
+pub fn set_http_status(ctx: &Context, status_code: u16, kind: SpanKind) {
+    let span = ctx.span();
+
+    if !span.is_recording() {
+        return;
+    }
+
+    span.set_attribute(KeyValue::new(
+        "http.response.status_code",
+        i64::from(status_code),
+    ));
+
+    let is_error = match kind {
+        SpanKind::Server => status_code >= 500,
+        _ => status_code >= 400,
+    };
+
+    if is_error {
+        span.set_status(Status::error(format!("HTTP {status_code}")));
+    }
+}
+
+/// Sets an attribute on the span of a Context.
+///
+/// Accepts the common scalar types, e.g. `&str`, `String`, `i64`, `f64` and `bool`.
+/// Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `key` - The attribute key
+/// * `value` - The attribute value
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn handle(user_id: i64) {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "handle");
+///     helpers::set_attribute(&ctx, "user.id", user_id);
+/// }
+/// ```
+pub fn set_attribute(ctx: &Context, key: &str, value: impl Into<Value>) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.set_attribute(KeyValue::new(key.to_owned(), value));
+    }
+}
+
+/// Sets several attributes on the span of a Context.
+///
+/// Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `attributes` - The attributes to set
+pub fn set_attributes(ctx: &Context, attributes: Vec<KeyValue>) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.set_attributes(attributes);
+    }
+}
+
+/// Renames the span of a Context.
+///
+/// This is useful when the meaningful name is only known after the span started, e.g. the
+/// route resolved by an HTTP framework or the SQL operation of a query. Samplers have
+/// already seen the initial name, so the new name does not change the sampling decision.
+/// Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `name` - The new name of the span
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn route(route: &str) {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Server, "GET");
+///     helpers::update_name(&ctx, &format!("GET {route}"));
+/// }
+/// ```
+pub fn update_name(ctx: &Context, name: &str) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.update_name(name.to_owned());
+    }
+}
+
+/// Adds an event to the span of a Context, timestamped now.
+///
+/// Events beyond the per-span event limit configured on the provider are dropped by the
+/// SDK. Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `name` - The name of the event
+/// * `attributes` - The attributes of the event
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::{KeyValue, global};
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn load(key: &str) {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Internal, "load");
+///     helpers::add_event(&ctx, "cache_miss", vec![KeyValue::new("cache.key", key.to_owned())]);
+/// }
+/// ```
+pub fn add_event(ctx: &Context, name: &str, attributes: Vec<KeyValue>) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.add_event(name.to_owned(), attributes);
+    }
+}
+
+/// Adds an event to the span of a Context at the specified time.
+///
+/// Events beyond the per-span event limit configured on the provider are dropped by the
+/// SDK. Does nothing if the span is not recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `name` - The name of the event
+/// * `timestamp` - The time of the event
+/// * `attributes` - The attributes of the event
+pub fn add_event_with_timestamp(
+    ctx: &Context,
+    name: &str,
+    timestamp: SystemTime,
+    attributes: Vec<KeyValue>,
+) {
+    let span = ctx.span();
+
+    if span.is_recording() {
+        span.add_event_with_timestamp(name.to_owned(), timestamp, attributes);
+    }
+}
+
+/// Runs a closure and records its duration as an event on the span of a Context.
+///
+/// The event is named `name` and carries the elapsed time in milliseconds as the
+/// `duration_ms` attribute, e.g. to annotate sub-operations within a single span. The
+/// closure always runs; the event is only added if the span is recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `name` - The name of the event
+/// * `f` - The closure to run
+///
+/// # Returns
+///
+/// The value returned by the closure
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::global;
+/// use opentelemetry::trace::SpanKind;
+/// use traces::helpers;
+///
+/// fn render(items: &[u64]) -> String {
+///     let tracer = global::tracer("my_service");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Internal, "render");
+///
+///     helpers::timed(&ctx, "serialize", || format!("{items:?}"))
+/// }
+/// ```
+pub fn timed<T>(ctx: &Context, name: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let value = f();
+    add_duration_event(ctx, name, start);
+
+    value
+}
+
+/// Awaits a future and records its duration as an event on the span of a Context.
+///
+/// This is the async counterpart of `timed`: the event is named `name` and carries the
+/// elapsed time in milliseconds, from the first poll to completion, as the `duration_ms`
+/// attribute. The future always runs; the event is only added if the span is recording.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context containing the span
+/// * `name` - The name of the event
+/// * `fut` - The future to await
+///
+/// # Returns
+///
+/// The output of the future
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::Context;
+/// use traces::helpers;
+///
+/// async fn fetch(ctx: &Context) -> u64 {
+///     helpers::timed_async(ctx, "db.query", async { 42 }).await
+/// }
+/// ```
+pub async fn timed_async<F: Future>(ctx: &Context, name: &str, fut: F) -> F::Output {
+    let start = Instant::now();
+    let value = fut.await;
+    add_duration_event(ctx, name, start);
+
+    value
+}
+
+/// Adds an event with the time elapsed since `start` as the `duration_ms` attribute.
+fn add_duration_event(ctx: &Context, name: &str, start: Instant) {
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+    add_event(ctx, name, vec![KeyValue::new("duration_ms", duration_ms)]);
+}
+
+/// Returns a copy of a Context with a baggage entry set.
+///
+/// Baggage is propagated over the wire to every downstream service by the baggage
+/// propagator, so it must not carry sensitive data such as credentials or personal data.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context to copy
+/// * `key` - The baggage key
+/// * `value` - The baggage value
+///
+/// # Returns
+///
+/// A new Context holding the existing baggage and the new entry
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::Context;
+/// use traces::helpers;
+///
+/// fn tag_tenant(ctx: &Context) -> Context {
+///     let ctx = helpers::set_baggage(ctx, "tenant.id", "acme");
+///     assert_eq!(helpers::get_baggage(&ctx, "tenant.id").as_deref(), Some("acme"));
+///     ctx
+/// }
+/// ```
+pub fn set_baggage(ctx: &Context, key: &str, value: &str) -> Context {
+    ctx.with_baggage(vec![KeyValue::new(key.to_owned(), value.to_owned())])
+}
+
+/// Reads a baggage entry from a Context.
+///
+/// # Arguments
+///
+/// * `ctx` - The Context holding the baggage
+/// * `key` - The baggage key
+///
+/// # Returns
+///
+/// The baggage value, or `None` if the entry is not set
+pub fn get_baggage(ctx: &Context, key: &str) -> Option<String> {
+    ctx.baggage()
+        .get(key)
+        .map(|value| value.as_str().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exporters::sampler::{RecordingSampler, SuppressingSampler},
+        test_utils,
+    };
+    use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider, ShouldSample, SpanData};
+    use std::{fmt, task::Poll, time::Duration};
+
+    /// An error with an optional cause.
+    #[derive(Debug)]
+    struct Failure {
+        message: &'static str,
+        cause: Option<Box<Failure>>,
+    }
+
+    impl fmt::Display for Failure {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.message)
+        }
+    }
+
+    impl Error for Failure {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.cause
+                .as_deref()
+                .map(|cause| cause as &(dyn Error + 'static))
+        }
+    }
+
+    /// Returns the value of an attribute, if set.
+    fn attribute(attributes: &[KeyValue], key: &str) -> Option<Value> {
+        attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.clone())
+    }
+
+    /// Creates a span, applies `f` to its context, ends it and returns the exported span.
+    fn span(f: impl FnOnce(&Context)) -> SpanData {
+        let pipeline = test_utils::pipeline();
+        let ctx = ctx(&pipeline.tracer, SpanKind::Internal, "span");
+        f(&ctx);
+        ctx.span().end();
+
+        pipeline.spans().pop().expect("exported span")
+    }
+
+    #[test]
+    fn record_error_sets_the_error_status_and_an_exception_per_cause() {
+        let err = Failure {
+            message: "request failed",
+            cause: Some(Box::new(Failure {
+                message: "connection reset",
+                cause: None,
+            })),
+        };
+
+        let span = span(|ctx| record_error(ctx, &err));
+
+        assert_eq!(span.status, Status::error("request failed"));
+        let messages: Vec<Option<Value>> = span
+            .events
+            .events
+            .iter()
+            .inspect(|event| assert_eq!(event.name, "exception"))
+            .map(|event| attribute(&event.attributes, "exception.message"))
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                Some(Value::from("request failed")),
+                Some(Value::from("connection reset")),
+            ]
+        );
+    }
+
+    #[test]
+    fn set_ok_sets_the_ok_status() {
+        let span = span(set_ok);
+
+        assert_eq!(span.status, Status::Ok);
+        assert!(span.events.events.is_empty());
+    }
+
+    #[test]
+    fn set_http_status_maps_the_code_per_span_kind() {
+        let cases = [
+            (SpanKind::Server, 200, Status::Unset),
+            (SpanKind::Server, 404, Status::Unset),
+            (SpanKind::Server, 503, Status::error("HTTP 503")),
+            (SpanKind::Client, 200, Status::Unset),
+            (SpanKind::Client, 404, Status::error("HTTP 404")),
+            (SpanKind::Client, 503, Status::error("HTTP 503")),
+        ];
+
+        for (kind, code, expected) in cases {
+            let span = span(|ctx| set_http_status(ctx, code, kind.clone()));
+
+            assert_eq!(span.status, expected, "{kind:?} {code}");
+            assert_eq!(
+                attribute(&span.attributes, "http.response.status_code"),
+                Some(Value::from(i64::from(code)))
+            );
+        }
+    }
+
+    #[test]
+    fn set_attribute_sets_scalar_attributes() {
+        let span = span(|ctx| {
+            set_attribute(ctx, "user.name", "ada");
+            set_attribute(ctx, "user.id", 42_i64);
+            set_attribute(ctx, "user.score", 0.5);
+            set_attribute(ctx, "user.admin", true);
+        });
+
+        assert_eq!(
+            attribute(&span.attributes, "user.name"),
+            Some(Value::from("ada"))
+        );
+        assert_eq!(attribute(&span.attributes, "user.id"), Some(Value::I64(42)));
+        assert_eq!(
+            attribute(&span.attributes, "user.score"),
+            Some(Value::F64(0.5))
+        );
+        assert_eq!(
+            attribute(&span.attributes, "user.admin"),
+            Some(Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn set_attributes_sets_every_attribute() {
+        let span = span(|ctx| {
+            set_attributes(
+                ctx,
+                vec![
+                    KeyValue::new("db.system", "postgresql"),
+                    KeyValue::new("db.operation", "SELECT"),
+                ],
+            )
+        });
+
+        assert_eq!(
+            attribute(&span.attributes, "db.system"),
+            Some(Value::from("postgresql"))
+        );
+        assert_eq!(
+            attribute(&span.attributes, "db.operation"),
+            Some(Value::from("SELECT"))
+        );
+    }
+
+    #[test]
+    fn add_event_keeps_the_events_in_order() {
+        let span = span(|ctx| {
+            add_event(
+                ctx,
+                "cache_miss",
+                vec![KeyValue::new("cache.key", "user:1")],
+            );
+            add_event(ctx, "db_query", vec![]);
+            add_event(ctx, "cache_fill", vec![]);
+        });
+
+        let names: Vec<&str> = span
+            .events
+            .events
+            .iter()
+            .map(|event| event.name.as_ref())
+            .collect();
+        assert_eq!(names, ["cache_miss", "db_query", "cache_fill"]);
+        assert_eq!(
+            attribute(&span.events.events[0].attributes, "cache.key"),
+            Some(Value::from("user:1"))
+        );
+        assert!(
+            span.events
+                .events
+                .windows(2)
+                .all(|events| events[0].timestamp <= events[1].timestamp)
+        );
+    }
+
+    #[test]
+    fn add_event_with_timestamp_keeps_the_timestamp() {
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        let span = span(|ctx| add_event_with_timestamp(ctx, "retry", timestamp, vec![]));
+
+        assert_eq!(span.events.events[0].name, "retry");
+        assert_eq!(span.events.events[0].timestamp, timestamp);
+    }
+
+    #[test]
+    fn ctx_with_links_links_the_span_to_each_context() {
+        let pipeline = test_utils::pipeline();
+        let first = ctx(&pipeline.tracer, SpanKind::Producer, "first");
+        let second = ctx(&pipeline.tracer, SpanKind::Producer, "second");
+        let links = [&first, &second].into_iter().filter_map(link).collect();
+
+        let batch = ctx_with_links(&pipeline.tracer, SpanKind::Consumer, "batch", links);
+        batch.span().end();
+
+        let span = pipeline.spans().pop().expect("exported span");
+        let linked: Vec<SpanContext> = span
+            .links
+            .links
+            .iter()
+            .map(|link| link.span_context.clone())
+            .collect();
+        assert_eq!(
+            linked,
+            [
+                first.span().span_context().clone(),
+                second.span().span_context().clone(),
+            ]
+        );
+        assert_eq!(span.links.dropped_count, 0);
+    }
+
+    #[test]
+    fn link_skips_a_context_without_span() {
+        assert!(link(&Context::new()).is_none());
+    }
+
+    /// Returns a future pending once before completing, like an `.await` on I/O.
+    fn yield_once() -> impl Future<Output = ()> {
+        let mut yielded = false;
+        std::future::poll_fn(move |cx| {
+            if yielded {
+                return Poll::Ready(());
+            }
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        })
+    }
+
+    #[test]
+    fn instrument_keeps_the_parent_across_await_points() {
+        let pipeline = test_utils::pipeline();
+        let parent = ctx(&pipeline.tracer, SpanKind::Server, "handle");
+        let parent_context = parent.span().span_context().clone();
+
+        test_utils::block_on(instrument(parent.clone(), async {
+            yield_once().await;
+            let child = ctx(&pipeline.tracer, SpanKind::Client, "call");
+            child.span().end();
+        }));
+        parent.span().end();
+
+        let spans = pipeline.spans();
+        let child = spans
+            .iter()
+            .find(|span| span.name == "call")
+            .expect("exported child span");
+        assert_eq!(child.parent_span_id, parent_context.span_id());
+        assert_eq!(child.span_context.trace_id(), parent_context.trace_id());
+    }
+
+    /// Creates a span on a pipeline sampling with `sampler` and returns its sampling state.
+    fn sampling_state<S: ShouldSample + 'static>(sampler: S) -> SamplingState {
+        let pipeline =
+            test_utils::pipeline_with(SdkTracerProvider::builder().with_sampler(sampler));
+
+        sampling_decision(&ctx(&pipeline.tracer, SpanKind::Internal, "span"))
+    }
+
+    #[test]
+    fn sampling_decision_reports_sampled_spans() {
+        assert_eq!(
+            sampling_state(Sampler::AlwaysOn),
+            SamplingState::RecordAndSample
+        );
+    }
+
+    #[test]
+    fn sampling_decision_reports_recorded_spans_not_sampled() {
+        assert_eq!(
+            sampling_state(RecordingSampler::new(Sampler::AlwaysOff)),
+            SamplingState::RecordOnly
+        );
+    }
+
+    #[test]
+    fn sampling_decision_reports_dropped_spans() {
+        assert_eq!(sampling_state(Sampler::AlwaysOff), SamplingState::Drop);
+    }
+
+    #[test]
+    fn sampling_decision_reports_suppressed_spans_as_dropped() {
+        let pipeline = test_utils::pipeline_with(
+            SdkTracerProvider::builder().with_sampler(SuppressingSampler::new(Sampler::AlwaysOn)),
+        );
+
+        let ctx = suppress(|| ctx(&pipeline.tracer, SpanKind::Internal, "span"));
+
+        assert_eq!(sampling_decision(&ctx), SamplingState::Drop);
+    }
+
+    #[test]
+    fn sampling_decision_reports_contexts_without_local_span() {
+        let remote = SpanContext::new(
+            TraceId::from(1_u128),
+            SpanId::from(1_u64),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+
+        assert_eq!(
+            sampling_decision(&Context::new()),
+            SamplingState::NotRecording
+        );
+        assert_eq!(
+            sampling_decision(&Context::new().with_remote_span_context(remote)),
+            SamplingState::NotRecording
+        );
+    }
+
+    /// Starts a root span on a pipeline sampling with `sampler` and returns its context.
+    fn root_ctx<S: ShouldSample + 'static>(sampler: S) -> Context {
+        let pipeline =
+            test_utils::pipeline_with(SdkTracerProvider::builder().with_sampler(sampler));
+
+        ctx(&pipeline.tracer, SpanKind::Internal, "root")
+    }
+
+    #[test]
+    fn traceparent_formats_a_sampled_span() {
+        let ctx = root_ctx(Sampler::AlwaysOn);
+        let span_context = ctx.span().span_context().clone();
+
+        assert_eq!(
+            traceparent(&ctx),
+            format!(
+                "00-{}-{}-01",
+                span_context.trace_id(),
+                span_context.span_id()
+            )
+        );
+        assert_eq!(trace_flags(&ctx), 1);
+    }
+
+    #[test]
+    fn traceparent_formats_a_recorded_span_not_sampled() {
+        let ctx = root_ctx(RecordingSampler::new(Sampler::AlwaysOff));
+        let span_context = ctx.span().span_context().clone();
+
+        assert_eq!(
+            traceparent(&ctx),
+            format!(
+                "00-{}-{}-00",
+                span_context.trace_id(),
+                span_context.span_id()
+            )
+        );
+        assert_eq!(trace_flags(&ctx), 0);
+    }
+
+    #[test]
+    fn traceparent_is_empty_for_a_span_not_recording() {
+        let ctx = root_ctx(Sampler::AlwaysOff);
+
+        assert_eq!(traceparent(&ctx), "");
+        assert_eq!(trace_flags(&ctx), 0);
+    }
+
+    #[cfg(feature = "otlp")]
+    #[test]
+    fn baggage_round_trips_through_grpc_metadata() {
+        use crate::{
+            extractors, injectors,
+            propagation::{self, DEFAULT_PROPAGATORS},
+        };
+
+        let _lock = test_utils::lock();
+        propagation::install(&DEFAULT_PROPAGATORS);
+        let pipeline = test_utils::pipeline();
+        let sent = set_baggage(
+            &ctx(&pipeline.tracer, SpanKind::Client, "call"),
+            "tenant.id",
+            "acme",
+        );
+
+        let mut metadata = tonic::metadata::MetadataMap::new();
+        injectors::inject(&sent, &mut metadata);
+        let received = extractors::extract(&metadata);
+
+        assert!(metadata.contains_key("baggage"));
+        assert_eq!(get_baggage(&received, "tenant.id"), Some("acme".to_owned()));
+        assert_eq!(get_baggage(&received, "user.id"), None);
+    }
+
+    #[test]
+    fn child_is_parented_to_the_provided_context() {
+        let pipeline = test_utils::pipeline();
+        let parent = ctx(&pipeline.tracer, SpanKind::Server, "parent");
+        let unrelated = ctx(&pipeline.tracer, SpanKind::Internal, "unrelated");
+        let _attached = unrelated.clone().attach();
+
+        let child = child(&pipeline.tracer, &parent, SpanKind::Client, "child");
+        child.span().end();
+
+        let exported = pipeline.spans().pop().expect("exported span");
+        assert_eq!(
+            exported.parent_span_id,
+            parent.span().span_context().span_id()
+        );
+        assert_eq!(
+            exported.span_context.trace_id(),
+            parent.span().span_context().trace_id()
+        );
+    }
+
+    #[test]
+    fn timed_records_the_duration_as_an_event() {
+        let span = span(|ctx| {
+            let value = timed(ctx, "db.query", || {
+                std::thread::sleep(Duration::from_millis(5));
+                42
+            });
+            assert_eq!(value, 42);
+        });
+
+        let event = span.events.events.first().expect("timed event");
+        assert_eq!(event.name, "db.query");
+        let Some(Value::F64(duration_ms)) = attribute(&event.attributes, "duration_ms") else {
+            panic!("duration_ms is not a float");
+        };
+        assert!((5.0..60_000.0).contains(&duration_ms));
+    }
+
+    #[test]
+    fn timed_runs_the_closure_when_the_span_is_not_recording() {
+        let mut ran = false;
+
+        timed(&Context::new(), "db.query", || ran = true);
+
+        assert!(ran);
+    }
+
+    #[test]
+    fn timed_async_records_the_duration_as_an_event() {
+        let span = span(|ctx| {
+            let value = test_utils::block_on(timed_async(ctx, "db.query", async { 42 }));
+            assert_eq!(value, 42);
+        });
+
+        let event = span.events.events.first().expect("timed event");
+        assert_eq!(event.name, "db.query");
+        assert!(matches!(
+            attribute(&event.attributes, "duration_ms"),
+            Some(Value::F64(duration_ms)) if duration_ms >= 0.0
+        ));
+    }
+
+    #[test]
+    fn current_ids_match_the_ids_of_the_attached_span() {
+        let pipeline = test_utils::pipeline();
+        let ctx = ctx(&pipeline.tracer, SpanKind::Internal, "current");
+
+        {
+            let _attached = ctx.clone().attach();
+
+            assert_eq!(current_trace_id(), trace_id(&ctx));
+            assert_eq!(current_span_id(), span_id(&ctx));
+            assert!(!current_trace_id().is_empty());
+        }
+
+        assert_eq!(current_trace_id(), "");
+        assert_eq!(current_span_id(), "");
+    }
+
+    #[test]
+    fn context_from_ids_continues_the_remote_trace() {
+        let ctx = context_from_ids(
+            "4bf92f3577b34da6a3ce929d0e0e4736",
+            " 00f067aa0ba902b7 ",
+            true,
+        )
+        .expect("valid ids");
+
+        let span_context = ctx.span().span_context().clone();
+        assert_eq!(
+            span_context.trace_id(),
+            TraceId::from(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736_u128)
+        );
+        assert_eq!(
+            span_context.span_id(),
+            SpanId::from(0x00f0_67aa_0ba9_02b7_u64)
+        );
+        assert!(span_context.is_sampled());
+        assert!(span_context.is_remote());
+
+        let unsampled = context_from_ids(
+            "4bf92f3577b34da6a3ce929d0e0e4736",
+            "00f067aa0ba902b7",
+            false,
+        )
+        .expect("valid ids");
+        assert!(!unsampled.span().span_context().is_sampled());
+    }
+
+    #[test]
+    fn context_from_ids_rejects_malformed_hex() {
+        assert_eq!(
+            context_from_ids("4bf92f3577b34da6a3ce929d0e0e473g", "00f067aa0ba902b7", true).err(),
+            Some(TracesError::ConversionError)
+        );
+        assert_eq!(
+            context_from_ids("4bf92f3577b34da6a3ce929d0e0e4736", "+0f067aa0ba902b7", true).err(),
+            Some(TracesError::ConversionError)
+        );
+    }
+
+    #[test]
+    fn context_from_ids_rejects_wrong_lengths_and_invalid_ids() {
+        for (trace_id, span_id) in [
+            ("4bf92f3577b34da6a3ce929d0e0e47", "00f067aa0ba902b7"),
+            ("4bf92f3577b34da6a3ce929d0e0e4736", "00f067aa0ba902"),
+            ("4bf92f3577b34da6a3ce929d0e0e4736ff", "00f067aa0ba902b7"),
+            ("00000000000000000000000000000000", "00f067aa0ba902b7"),
+            ("4bf92f3577b34da6a3ce929d0e0e4736", "0000000000000000"),
+        ] {
+            assert_eq!(
+                context_from_ids(trace_id, span_id, true).err(),
+                Some(TracesError::ConversionError),
+                "{trace_id} {span_id}"
+            );
+        }
+    }
+
+    #[test]
+    fn update_name_renames_the_exported_span() {
+        let span = span(|ctx| update_name(ctx, "GET /users/{id}"));
+
+        assert_eq!(span.name, "GET /users/{id}");
+    }
+
+    #[test]
+    fn is_sampled_reports_a_sampled_span() {
+        assert!(is_sampled(&root_ctx(Sampler::AlwaysOn)));
+    }
+
+    #[test]
+    fn is_sampled_reports_a_recorded_span_not_sampled() {
+        assert!(!is_sampled(&root_ctx(RecordingSampler::new(
+            Sampler::AlwaysOff
+        ))));
+    }
+
+    #[test]
+    fn is_sampled_reports_a_context_not_recording() {
+        let remote = context_from_ids("4bf92f3577b34da6a3ce929d0e0e4736", "00f067aa0ba902b7", true)
+            .expect("valid ids");
+
+        assert!(!is_sampled(&Context::new()));
+        assert!(!is_sampled(&remote));
+    }
+}
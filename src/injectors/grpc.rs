@@ -7,11 +7,8 @@
 //! This module provides functionality to inject OpenTelemetry context
 //! into gRPC metadata, allowing distributed tracing across gRPC service boundaries.
 
-use opentelemetry::{
-    global::{self},
-    propagation::Injector,
-    Context,
-};
+use crate::injectors::{self, IntoCarrier};
+use opentelemetry::{Context, propagation::Injector};
 
 /// An OpenTelemetry context injector for gRPC requests.
 ///
@@ -44,9 +41,18 @@ impl Injector for GRPCInjector<'_> {
     /// * `key` - The metadata key to set
     /// * `value` - The value to set for the given key
     fn set(&mut self, key: &str, value: String) {
+        self.0.carrier_set(key, value)
+    }
+}
+
+impl IntoCarrier for tonic::metadata::MetadataMap {
+    /// Sets a key and value in the gRPC MetadataMap.
+    ///
+    /// Does nothing if the key or value cannot be converted into valid metadata.
+    fn carrier_set(&mut self, key: &str, value: String) {
         if let Ok(key) = tonic::metadata::MetadataKey::from_bytes(key.as_bytes()) {
             if let Ok(val) = tonic::metadata::MetadataValue::try_from(&value) {
-                self.0.insert(key, val);
+                self.insert(key, val);
             }
         }
     }
@@ -77,7 +83,75 @@ impl Injector for GRPCInjector<'_> {
 /// }
 /// ```
 pub fn inject(ctx: &Context, meta: &mut tonic::metadata::MetadataMap) {
-    global::get_text_map_propagator(|propagator| {
-        propagator.inject_context(ctx, &mut GRPCInjector(meta))
-    });
+    injectors::inject(ctx, meta);
+}
+
+/// Injects trace context into gRPC trailers.
+///
+/// This function injects the trace context into the trailer metadata of a response,
+/// allowing clients of streaming calls to correlate the response with the server trace.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `trailers` - Mutable reference to the trailer metadata where the context will be injected
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::Context;
+/// use tonic::{Code, Status, metadata::MetadataMap};
+/// use traces::injectors::grpc;
+///
+/// fn finish_stream(ctx: &Context) -> Status {
+///     let mut trailers = MetadataMap::new();
+///     grpc::inject_trailers(ctx, &mut trailers);
+///     Status::with_metadata(Code::Ok, "done", trailers)
+/// }
+/// ```
+pub fn inject_trailers(ctx: &Context, trailers: &mut tonic::metadata::MetadataMap) {
+    injectors::inject(ctx, trailers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extractors, test_utils};
+
+    #[test]
+    fn round_trips_the_context_through_the_metadata() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut metadata = tonic::metadata::MetadataMap::new();
+        inject(&sent, &mut metadata);
+
+        assert!(metadata.contains_key("traceparent"));
+        test_utils::assert_propagated(&sent, &extractors::extract(&metadata));
+    }
+
+    #[test]
+    fn sets_ascii_keys() {
+        let mut metadata = tonic::metadata::MetadataMap::new();
+
+        metadata.carrier_set("x-tenant", "acme".to_owned());
+
+        assert_eq!(
+            metadata
+                .get("x-tenant")
+                .and_then(|value| value.to_str().ok()),
+            Some("acme")
+        );
+    }
+
+    #[test]
+    fn skips_binary_keys() {
+        let mut metadata = tonic::metadata::MetadataMap::new();
+
+        metadata.carrier_set("x-tenant-bin", "acme".to_owned());
+
+        assert!(metadata.is_empty());
+    }
 }
@@ -0,0 +1,82 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! HashMap trace context injector.
+//!
+//! This module provides functionality to inject OpenTelemetry context
+//! into plain string maps, such as HTTP headers or message bus headers.
+
+use crate::injectors::{self, IntoCarrier};
+use opentelemetry::{Context, propagation::Injector};
+use std::collections::HashMap;
+
+/// An OpenTelemetry context injector for string maps.
+///
+/// This struct implements the `Injector` trait to allow injecting trace context
+/// into a `HashMap<String, String>`.
+pub struct HashMapInjector<'a>(&'a mut HashMap<String, String>);
+
+impl<'a> HashMapInjector<'a> {
+    /// Creates a new `HashMapInjector` from a mutable string map.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - Mutable reference to a string map
+    ///
+    /// # Returns
+    ///
+    /// A new `HashMapInjector` instance
+    pub fn new(m: &'a mut HashMap<String, String>) -> HashMapInjector<'a> {
+        HashMapInjector(m)
+    }
+}
+
+impl Injector for HashMapInjector<'_> {
+    /// Sets a key and value in the map.
+    ///
+    /// The key is lowercased for HTTP compatibility.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to set
+    /// * `value` - The value to set for the given key
+    fn set(&mut self, key: &str, value: String) {
+        self.0.carrier_set(key, value)
+    }
+}
+
+impl IntoCarrier for HashMap<String, String> {
+    /// Sets a key and value in the map, lowercasing the key.
+    fn carrier_set(&mut self, key: &str, value: String) {
+        self.insert(key.to_lowercase(), value);
+    }
+}
+
+/// Injects trace context into a string map.
+///
+/// This function injects the current trace context into the map
+/// so that it can be propagated to the next service in the call chain.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `map` - Mutable reference to the map where the context will be injected
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::Context;
+/// use std::collections::HashMap;
+/// use traces::injectors::hashmap;
+///
+/// fn make_http_call(ctx: &Context) {
+///     let mut headers = HashMap::new();
+///     // Inject trace context into the headers
+///     hashmap::inject(ctx, &mut headers);
+///     // Now send the headers with your request
+/// }
+/// ```
+pub fn inject(ctx: &Context, map: &mut HashMap<String, String>) {
+    injectors::inject(ctx, map);
+}
@@ -0,0 +1,200 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! HTTP trace context injector.
+//!
+//! This module provides functionality to inject OpenTelemetry context
+//! into the `http` crate `HeaderMap`, as used by hyper, axum and reqwest.
+
+use crate::injectors::{self, IntoCarrier};
+use ::http::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::{Context, propagation::Injector};
+
+/// An OpenTelemetry context injector for HTTP requests.
+///
+/// This struct implements the `Injector` trait to allow injecting trace context
+/// into HTTP headers.
+pub struct HTTPInjector<'a>(&'a mut HeaderMap);
+
+impl<'a> HTTPInjector<'a> {
+    /// Creates a new `HTTPInjector` from mutable HTTP headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Mutable reference to the HTTP headers
+    ///
+    /// # Returns
+    ///
+    /// A new `HTTPInjector` instance
+    pub fn new(h: &'a mut HeaderMap) -> HTTPInjector<'a> {
+        HTTPInjector(h)
+    }
+}
+
+impl Injector for HTTPInjector<'_> {
+    /// Sets a key and value in the HTTP headers.
+    ///
+    /// Does nothing if the key or value cannot be converted into a valid header.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header name to set
+    /// * `value` - The value to set for the given key
+    fn set(&mut self, key: &str, value: String) {
+        self.0.carrier_set(key, value)
+    }
+}
+
+impl IntoCarrier for HeaderMap {
+    /// Sets a key and value in the HTTP headers, replacing the existing values of the key.
+    ///
+    /// Does nothing if the key or value cannot be converted into a valid header.
+    fn carrier_set(&mut self, key: &str, value: String) {
+        if let Ok(name) = HeaderName::from_bytes(key.as_bytes())
+            && let Ok(value) = HeaderValue::from_str(&value)
+        {
+            self.insert(name, value);
+        }
+    }
+}
+
+/// Injects trace context into HTTP headers.
+///
+/// This function injects the trace context into the headers of an outgoing request
+/// so that it can be propagated to the next service in the call chain.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `headers` - Mutable reference to the HTTP headers where the context will be injected
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use http::HeaderMap;
+/// use opentelemetry::Context;
+/// use traces::injectors;
+///
+/// fn call_downstream(ctx: &Context) {
+///     let mut headers = HeaderMap::new();
+///     // Inject trace context into the headers
+///     injectors::http::inject(ctx, &mut headers);
+///     // Now send the request with the headers
+/// }
+/// ```
+pub fn inject(ctx: &Context, headers: &mut HeaderMap) {
+    injectors::inject(ctx, headers);
+}
+
+/// Injects trace context into an outgoing `reqwest` request.
+///
+/// The context is injected into a `HeaderMap` added to the request headers, replacing
+/// the propagation headers already set on the builder.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `builder` - The request builder where the context will be injected
+///
+/// # Returns
+///
+/// The request builder with the propagation headers, for chaining
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::Context;
+/// use traces::injectors;
+///
+/// async fn call_downstream(ctx: &Context, client: &reqwest::Client) -> reqwest::Result<()> {
+///     injectors::http::inject_into_request(ctx, client.get("http://users/42"))
+///         .send()
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "reqwest")]
+pub fn inject_into_request(
+    ctx: &Context,
+    builder: reqwest::RequestBuilder,
+) -> reqwest::RequestBuilder {
+    let mut headers = HeaderMap::new();
+    inject(ctx, &mut headers);
+    builder.headers(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extractors, test_utils};
+    use opentelemetry::trace::{Span, SpanKind, TraceContextExt};
+
+    #[test]
+    fn round_trips_the_context_through_the_headers() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = HeaderMap::new();
+        inject(&sent, &mut headers);
+
+        assert!(headers.contains_key("traceparent"));
+        test_utils::assert_propagated(&sent, &extractors::extract(&headers));
+    }
+
+    #[test]
+    fn starts_the_server_span_as_a_child_of_the_client() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = HeaderMap::new();
+        inject(&sent, &mut headers);
+        let (_ctx, mut span) = extractors::http::span(&headers, &pipeline.tracer, "GET /users");
+        span.end();
+
+        let served = pipeline.spans().pop().expect("exported span");
+        assert_eq!(served.name, "GET /users");
+        assert_eq!(served.span_kind, SpanKind::Server);
+        assert_eq!(
+            served.span_context.trace_id(),
+            sent.span().span_context().trace_id()
+        );
+        assert_eq!(served.parent_span_id, sent.span().span_context().span_id());
+    }
+
+    #[test]
+    fn extracts_an_empty_context_from_headers_without_trace_context() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+
+        let ctx = extractors::extract(&HeaderMap::new());
+
+        assert!(!ctx.span().span_context().is_valid());
+    }
+
+    #[cfg(feature = "reqwest")]
+    #[test]
+    fn injects_the_context_into_a_reqwest_request() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let request = inject_into_request(&sent, reqwest::Client::new().get("http://users/42"))
+            .build()
+            .expect("request");
+
+        assert_eq!(
+            request
+                .headers()
+                .get("traceparent")
+                .and_then(|value| value.to_str().ok()),
+            Some(crate::helpers::traceparent(&sent).as_str())
+        );
+        test_utils::assert_propagated(&sent, &extractors::extract(request.headers()));
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Kafka trace context injector.
+//!
+//! This module provides functionality to inject OpenTelemetry context
+//! into Kafka record headers, allowing distributed tracing across the message bus.
+
+use crate::injectors::{self, IntoCarrier};
+use opentelemetry::{Context, propagation::Injector};
+use rdkafka::message::{Header, OwnedHeaders};
+use std::mem;
+
+/// An OpenTelemetry context injector for Kafka records.
+///
+/// This struct implements the `Injector` trait to allow injecting trace context
+/// into the headers of a Kafka record.
+pub struct KafkaInjector<'a>(&'a mut OwnedHeaders);
+
+impl<'a> KafkaInjector<'a> {
+    /// Creates a new `KafkaInjector` from mutable record headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Mutable reference to the record headers
+    ///
+    /// # Returns
+    ///
+    /// A new `KafkaInjector` instance
+    pub fn new(h: &'a mut OwnedHeaders) -> KafkaInjector<'a> {
+        KafkaInjector(h)
+    }
+}
+
+impl Injector for KafkaInjector<'_> {
+    /// Appends a key and value to the record headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header key to set
+    /// * `value` - The value to set for the given key
+    fn set(&mut self, key: &str, value: String) {
+        self.0.carrier_set(key, value)
+    }
+}
+
+impl IntoCarrier for OwnedHeaders {
+    /// Appends a key and value to the record headers.
+    ///
+    /// Kafka headers may repeat keys; the extractor reads the last value of a key, so an
+    /// injected value takes precedence over one already present.
+    fn carrier_set(&mut self, key: &str, value: String) {
+        let headers = mem::replace(self, OwnedHeaders::new());
+        *self = headers.insert(Header {
+            key,
+            value: Some(&value),
+        });
+    }
+}
+
+/// Injects trace context into Kafka record headers.
+///
+/// This function injects the trace context into the headers of a record
+/// so that it can be propagated to its consumers.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `headers` - Mutable reference to the record headers where the context will be injected
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use opentelemetry::Context;
+/// use rdkafka::{message::OwnedHeaders, producer::FutureRecord};
+/// use traces::injectors::kafka;
+///
+/// fn produce(ctx: &Context, payload: &str) {
+///     let mut headers = OwnedHeaders::new();
+///     // Inject trace context into the headers
+///     kafka::inject(ctx, &mut headers);
+///     let record = FutureRecord::<(), _>::to("events")
+///         .payload(payload)
+///         .headers(headers);
+///     // Now send the record with your producer
+/// }
+/// ```
+pub fn inject(ctx: &Context, headers: &mut OwnedHeaders) {
+    injectors::inject(ctx, headers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extractors, test_utils};
+    use opentelemetry::trace::{Span, TraceContextExt};
+    use rdkafka::message::Headers;
+
+    #[test]
+    fn round_trips_the_context_through_the_record_headers() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = OwnedHeaders::new();
+        inject(&sent, &mut headers);
+
+        test_utils::assert_propagated(&sent, &extractors::extract(&headers));
+    }
+
+    #[test]
+    fn starts_the_consumer_span_as_a_child_of_the_producer() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = OwnedHeaders::new();
+        inject(&sent, &mut headers);
+        let (_ctx, mut span) = extractors::kafka::span(&headers, &pipeline.tracer);
+        span.end();
+
+        let consumed = pipeline.spans().pop().expect("exported span");
+        assert_eq!(consumed.name, "Kafka");
+        assert_eq!(
+            consumed.span_context.trace_id(),
+            sent.span().span_context().trace_id()
+        );
+        assert_eq!(
+            consumed.parent_span_id,
+            sent.span().span_context().span_id()
+        );
+    }
+
+    #[test]
+    fn extracts_the_last_injected_context() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let stale = test_utils::sent_context(&pipeline);
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = OwnedHeaders::new();
+        inject(&stale, &mut headers);
+        inject(&sent, &mut headers);
+
+        assert!(headers.count() > 2);
+        test_utils::assert_propagated(&sent, &extractors::extract(&headers));
+    }
+}
@@ -6,6 +6,60 @@
 //!
 //! This module provides injectors for propagating trace context information
 //! into various transport protocols and formats.
+//!
+//! Any transport can be supported by implementing `IntoCarrier` for its header
+//! container, which provides the `inject` helper for free.
+
+use opentelemetry::{Context, global, propagation::Injector};
 
 #[cfg(feature = "otlp")]
 pub mod grpc;
+pub mod hashmap;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+/// A transport carrier into which trace context can be injected.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use traces::injectors::IntoCarrier;
+///
+/// struct Headers(BTreeMap<String, String>);
+///
+/// impl IntoCarrier for Headers {
+///     fn carrier_set(&mut self, key: &str, value: String) {
+///         self.0.insert(key.to_owned(), value);
+///     }
+/// }
+/// ```
+pub trait IntoCarrier {
+    /// Stores a value for a key, skipping it if the transport cannot represent it.
+    fn carrier_set(&mut self, key: &str, value: String);
+}
+
+/// An OpenTelemetry `Injector` over any `IntoCarrier`.
+struct CarrierInjector<'a, C: ?Sized>(&'a mut C);
+
+impl<C: IntoCarrier + ?Sized> Injector for CarrierInjector<'_, C> {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.carrier_set(key, value)
+    }
+}
+
+/// Injects trace context into a carrier using the global propagator.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `carrier` - The carrier where the context will be injected
+pub fn inject<C: IntoCarrier + ?Sized>(ctx: &Context, carrier: &mut C) {
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(ctx, &mut CarrierInjector(carrier))
+    });
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! NATS trace context injector.
+//!
+//! This module provides functionality to inject OpenTelemetry context
+//! into NATS message headers, allowing distributed tracing across subjects.
+
+use crate::injectors::{self, IntoCarrier};
+use async_nats::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::{Context, propagation::Injector};
+
+/// An OpenTelemetry context injector for NATS messages.
+///
+/// This struct implements the `Injector` trait to allow injecting trace context
+/// into the headers of a NATS message.
+pub struct NatsInjector<'a>(&'a mut HeaderMap);
+
+impl<'a> NatsInjector<'a> {
+    /// Creates a new `NatsInjector` from mutable NATS message headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `h` - Mutable reference to the message headers
+    ///
+    /// # Returns
+    ///
+    /// A new `NatsInjector` instance
+    pub fn new(h: &'a mut HeaderMap) -> NatsInjector<'a> {
+        NatsInjector(h)
+    }
+}
+
+impl Injector for NatsInjector<'_> {
+    /// Sets a key and value in the NATS message headers.
+    ///
+    /// Does nothing if the key or value cannot be converted into a valid header.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The header name to set
+    /// * `value` - The value to set for the given key
+    fn set(&mut self, key: &str, value: String) {
+        self.0.carrier_set(key, value)
+    }
+}
+
+impl IntoCarrier for HeaderMap {
+    /// Sets a key and value in the NATS message headers, replacing the existing values of
+    /// the key.
+    ///
+    /// Does nothing if the key or value cannot be converted into a valid header.
+    fn carrier_set(&mut self, key: &str, value: String) {
+        if let Ok(name) = key.parse::<HeaderName>()
+            && let Ok(value) = value.parse::<HeaderValue>()
+        {
+            self.insert(name, value);
+        }
+    }
+}
+
+/// Injects trace context into NATS message headers.
+///
+/// This function injects the trace context into the headers of an outgoing message
+/// so that it can be propagated to the subscribers.
+///
+/// # Arguments
+///
+/// * `ctx` - The OpenTelemetry context to propagate
+/// * `headers` - Mutable reference to the message headers where the context will be injected
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_nats::{Client, HeaderMap};
+/// use opentelemetry::Context;
+/// use traces::injectors;
+///
+/// async fn publish(client: &Client, ctx: &Context) {
+///     let mut headers = HeaderMap::new();
+///     injectors::nats::inject(ctx, &mut headers);
+///     client
+///         .publish_with_headers("orders.created", headers, "{}".into())
+///         .await
+///         .expect("Failed to publish");
+/// }
+/// ```
+pub fn inject(ctx: &Context, headers: &mut HeaderMap) {
+    injectors::inject(ctx, headers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extractors, helpers, test_utils};
+    use opentelemetry::trace::{Span, SpanKind, TraceContextExt};
+
+    #[test]
+    fn round_trips_the_context_through_the_message_headers() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = HeaderMap::new();
+        inject(&sent, &mut headers);
+
+        test_utils::assert_propagated(&sent, &extractors::extract(&headers));
+    }
+
+    #[test]
+    fn starts_the_consumer_span_as_a_child_of_the_publisher() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = test_utils::sent_context(&pipeline);
+
+        let mut headers = HeaderMap::new();
+        inject(&sent, &mut headers);
+        let (_ctx, mut span) = extractors::nats::span(&headers, &pipeline.tracer, "orders.created");
+        span.end();
+
+        let consumed = pipeline.spans().pop().expect("exported span");
+        assert_eq!(consumed.name, "orders.created");
+        assert_eq!(consumed.span_kind, SpanKind::Consumer);
+        assert_eq!(
+            consumed.span_context.trace_id(),
+            sent.span().span_context().trace_id()
+        );
+        assert_eq!(
+            consumed.parent_span_id,
+            sent.span().span_context().span_id()
+        );
+    }
+
+    #[test]
+    fn extracts_headers_regardless_of_their_case() {
+        let _lock = test_utils::lock();
+        test_utils::install_propagators();
+        let pipeline = test_utils::pipeline();
+        let sent = helpers::ctx(&pipeline.tracer, SpanKind::Producer, "send");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Traceparent", helpers::traceparent(&sent).as_str());
+        let received = extractors::extract(&headers);
+
+        assert_eq!(
+            received.span().span_context().span_id(),
+            sent.span().span_context().span_id()
+        );
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Axum server tracing middleware.
+//!
+//! This module provides a tower layer for axum routers that creates a server span per
+//! HTTP request. The span is a child of the context extracted from the request headers,
+//! is current while the handler runs and ends when the response is produced.
+
+use crate::{extractors, helpers, provider};
+use ::axum::extract::MatchedPath;
+use opentelemetry::{
+    KeyValue,
+    trace::{FutureExt, SpanKind, Status, TraceContextExt, Tracer},
+};
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A tower layer tracing the HTTP requests of an axum router.
+///
+/// Each request gets a `Server` span named after the method and the matched route, e.g.
+/// `GET /users/{id}`, falling back to the request path when no route matched. The span
+/// carries the `http.request.method`, `url.path` and `http.route` attributes, and the
+/// `http.response.status_code` attribute and status as set by `helpers::set_http_status`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use axum::{Router, routing::get};
+/// use traces::integration::axum::TraceLayer;
+///
+/// fn router() -> Router {
+///     Router::new()
+///         .route("/users/{id}", get(|| async { "user" }))
+///         .layer(TraceLayer::new())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceLayer;
+
+impl TraceLayer {
+    /// Creates a new `TraceLayer`.
+    ///
+    /// # Returns
+    ///
+    /// A new `TraceLayer` instance
+    pub fn new() -> TraceLayer {
+        TraceLayer
+    }
+}
+
+impl<S> Layer<S> for TraceLayer {
+    type Service = TraceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceService { inner }
+    }
+}
+
+/// A tower service tracing the HTTP requests of the wrapped service, see `TraceLayer`.
+#[derive(Debug, Clone)]
+pub struct TraceService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for TraceService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Display,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let parent = extractors::extract(request.headers());
+
+        let method = request.method().as_str().to_owned();
+        let path = request.uri().path().to_owned();
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|matched| matched.as_str().to_owned());
+
+        let mut attributes = vec![
+            KeyValue::new("http.request.method", method.clone()),
+            KeyValue::new("url.path", path.clone()),
+        ];
+        if let Some(route) = &route {
+            attributes.push(KeyValue::new("http.route", route.clone()));
+        }
+
+        let tracer = provider::tracer();
+        let span = tracer
+            .span_builder(format!("{method} {}", route.unwrap_or(path)))
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start_with_context(&tracer, &parent);
+        let ctx = parent.with_span(span);
+
+        let response = {
+            let _attached = ctx.clone().attach();
+            self.inner.call(request)
+        };
+
+        Box::pin(async move {
+            let result = response.with_context(ctx.clone()).await;
+
+            match &result {
+                Ok(response) => {
+                    helpers::set_http_status(&ctx, response.status().as_u16(), SpanKind::Server)
+                }
+                Err(err) => ctx.span().set_status(Status::error(err.to_string())),
+            }
+
+            ctx.span().end();
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use ::axum::{Router, body::Body, routing::get};
+    use opentelemetry::global;
+
+    #[test]
+    fn creates_a_server_span_per_request() {
+        let _lock = test_utils::lock();
+        let pipeline = test_utils::pipeline();
+        global::set_tracer_provider(pipeline.provider.clone());
+        let mut router = Router::new()
+            .route("/users/{id}", get(|| async { "user" }))
+            .layer(TraceLayer::new());
+
+        let request = http::Request::builder()
+            .method("GET")
+            .uri("/users/42")
+            .body(Body::empty())
+            .expect("request");
+        let response = test_utils::block_on(router.call(request)).expect("response");
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let spans = pipeline.spans();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, "GET /users/{id}");
+        assert_eq!(spans[0].span_kind, SpanKind::Server);
+        for attribute in [
+            KeyValue::new("http.request.method", "GET"),
+            KeyValue::new("url.path", "/users/42"),
+            KeyValue::new("http.route", "/users/{id}"),
+            KeyValue::new("http.response.status_code", 200_i64),
+        ] {
+            assert!(
+                spans[0].attributes.contains(&attribute),
+                "missing {attribute:?}"
+            );
+        }
+    }
+}
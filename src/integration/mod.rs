@@ -0,0 +1,14 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Framework integrations.
+//!
+//! This module contains middlewares that trace requests automatically, so services do not
+//! need to wire the extractors into each handler. Each integration is conditionally
+//! compiled based on feature flags.
+
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "otlp")]
+pub mod tonic;
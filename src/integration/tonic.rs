@@ -0,0 +1,341 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Tonic server tracing middleware.
+//!
+//! This module provides a tower layer for tonic servers that creates a server span per
+//! gRPC call. The span is a child of the context extracted from the request metadata,
+//! is current while the handler runs and ends when the response is produced.
+
+use crate::{extractors, provider};
+use ::tonic::{Code, metadata::MetadataMap};
+use http::HeaderMap;
+use http_body::{Body, Frame, SizeHint};
+use opentelemetry::{
+    KeyValue,
+    trace::{FutureExt, SpanKind, Status, TraceContextExt, Tracer},
+};
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A tower layer tracing the gRPC calls of a tonic server.
+///
+/// Each call gets a `Server` span named after the gRPC method, e.g.
+/// `helloworld.Greeter/SayHello`, with the `rpc.system`, `rpc.service`, `rpc.method` and
+/// `rpc.grpc.status_code` attributes. The span status is set to error when the call fails
+/// with a non-`Ok` gRPC status. The status is read from the response headers for
+/// trailers-only responses, and from the trailers otherwise, so the span ends once the
+/// response body is fully sent, or dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tonic::transport::Server;
+/// use traces::integration::tonic::TraceLayer;
+///
+/// async fn serve() {
+///     let server = Server::builder().layer(TraceLayer::new());
+///     // Add the services and serve
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TraceLayer;
+
+impl TraceLayer {
+    /// Creates a new `TraceLayer`.
+    ///
+    /// # Returns
+    ///
+    /// A new `TraceLayer` instance
+    pub fn new() -> TraceLayer {
+        TraceLayer
+    }
+}
+
+impl<S> Layer<S> for TraceLayer {
+    type Service = TraceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceService { inner }
+    }
+}
+
+/// A tower service tracing the gRPC calls of the wrapped service, see `TraceLayer`.
+#[derive(Debug, Clone)]
+pub struct TraceService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for TraceService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: Display,
+    ResBody: Body + 'static,
+{
+    type Response = http::Response<TracedBody<ResBody>>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let metadata = MetadataMap::from_headers(request.headers().clone());
+        let parent = extractors::extract(&metadata);
+
+        let name = request.uri().path().trim_start_matches('/').to_owned();
+        let (service, method) = name.split_once('/').unwrap_or((name.as_str(), ""));
+        let attributes = vec![
+            KeyValue::new("rpc.system", "grpc"),
+            KeyValue::new("rpc.service", service.to_owned()),
+            KeyValue::new("rpc.method", method.to_owned()),
+        ];
+
+        let tracer = provider::tracer();
+        let span = tracer
+            .span_builder(name)
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start_with_context(&tracer, &parent);
+        let ctx = parent.with_span(span);
+
+        let response = {
+            let _attached = ctx.clone().attach();
+            self.inner.call(request)
+        };
+
+        Box::pin(async move {
+            match response.with_context(ctx.clone()).await {
+                Ok(response) => {
+                    // Trailers-only responses carry the status in the headers
+                    record_status(&ctx, response.headers());
+
+                    Ok(response.map(|body| TracedBody {
+                        inner: Box::pin(body),
+                        ctx: Some(ctx),
+                    }))
+                }
+                Err(err) => {
+                    let span = ctx.span();
+                    span.set_status(Status::error(err.to_string()));
+                    span.end();
+                    Err(err)
+                }
+            }
+        })
+    }
+}
+
+/// A response body ending the span of its gRPC call, see `TraceLayer`.
+///
+/// The span ends when the trailers are sent, when the body ends without trailers, or when
+/// the body is dropped, e.g. because the client cancelled the call.
+pub struct TracedBody<B> {
+    inner: Pin<Box<B>>,
+    ctx: Option<opentelemetry::Context>,
+}
+
+impl<B> TracedBody<B> {
+    /// Ends the span of the call, once.
+    fn finish(&mut self) {
+        if let Some(ctx) = self.ctx.take() {
+            ctx.span().end();
+        }
+    }
+}
+
+impl<B: Body> Body for TracedBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        let poll = this.inner.as_mut().poll_frame(cx);
+
+        match &poll {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(trailers) = frame.trailers_ref() {
+                    if let Some(ctx) = &this.ctx {
+                        record_status(ctx, trailers);
+                    }
+                    this.finish();
+                }
+            }
+            Poll::Ready(Some(Err(_))) => {
+                if let Some(ctx) = &this.ctx {
+                    ctx.span()
+                        .set_status(Status::error("failure to send the response body"));
+                }
+                this.finish();
+            }
+            Poll::Ready(None) => this.finish(),
+            Poll::Pending => {}
+        }
+
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B> Drop for TracedBody<B> {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Records the gRPC status of headers or trailers on the span of a call, if present.
+fn record_status(ctx: &opentelemetry::Context, headers: &HeaderMap) {
+    let Some(status) = headers.get("grpc-status") else {
+        return;
+    };
+
+    let code = Code::from_bytes(status.as_bytes());
+    let span = ctx.span();
+    span.set_attribute(KeyValue::new("rpc.grpc.status_code", code as i64));
+
+    if code != Code::Ok {
+        span.set_status(Status::error(code.description()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use opentelemetry::global;
+    use std::{
+        convert::Infallible,
+        future::{Ready, poll_fn, ready},
+    };
+
+    /// A response body sending a gRPC status in its trailers.
+    struct TrailersBody(Option<HeaderMap>);
+
+    impl Body for TrailersBody {
+        type Data = &'static [u8];
+        type Error = Infallible;
+
+        fn poll_frame(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            Poll::Ready(
+                self.get_mut()
+                    .0
+                    .take()
+                    .map(|trailers| Ok(Frame::trailers(trailers))),
+            )
+        }
+    }
+
+    /// A gRPC service answering every call with a status in the trailers.
+    #[derive(Clone)]
+    struct Greeter(Code);
+
+    impl Service<http::Request<()>> for Greeter {
+        type Response = http::Response<TrailersBody>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<()>) -> Self::Future {
+            let mut trailers = HeaderMap::new();
+            trailers.insert("grpc-status", (self.0 as i32).into());
+            ready(Ok(http::Response::new(TrailersBody(Some(trailers)))))
+        }
+    }
+
+    fn call(service: &mut TraceService<Greeter>) -> http::Response<TracedBody<TrailersBody>> {
+        let request = http::Request::builder()
+            .uri("/helloworld.Greeter/SayHello")
+            .body(())
+            .expect("request");
+
+        test_utils::block_on(service.call(request)).expect("response")
+    }
+
+    fn drain(body: &mut TracedBody<TrailersBody>) {
+        while test_utils::block_on(poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx))).is_some() {}
+    }
+
+    #[test]
+    fn creates_a_server_span_per_call() {
+        let _lock = test_utils::lock();
+        let pipeline = test_utils::pipeline();
+        global::set_tracer_provider(pipeline.provider.clone());
+        let mut ok = TraceLayer::new().layer(Greeter(Code::Ok));
+        let mut not_found = TraceLayer::new().layer(Greeter(Code::NotFound));
+
+        let mut response = call(&mut ok);
+        assert!(
+            pipeline.spans().is_empty(),
+            "the span ends with the trailers"
+        );
+        drain(response.body_mut());
+        drain(call(&mut not_found).body_mut());
+
+        let spans = pipeline.spans();
+        assert_eq!(spans.len(), 2);
+        for span in &spans {
+            assert_eq!(span.name, "helloworld.Greeter/SayHello");
+            assert_eq!(span.span_kind, SpanKind::Server);
+            assert!(
+                span.attributes
+                    .contains(&KeyValue::new("rpc.method", "SayHello"))
+            );
+        }
+        assert!(
+            spans[0]
+                .attributes
+                .contains(&KeyValue::new("rpc.grpc.status_code", 0_i64))
+        );
+        assert_eq!(spans[0].status, Status::Unset);
+        assert!(
+            spans[1]
+                .attributes
+                .contains(&KeyValue::new("rpc.grpc.status_code", 5_i64))
+        );
+        assert!(matches!(spans[1].status, Status::Error { .. }));
+    }
+
+    #[test]
+    fn ends_the_span_when_the_body_is_dropped() {
+        let _lock = test_utils::lock();
+        let pipeline = test_utils::pipeline();
+        global::set_tracer_provider(pipeline.provider.clone());
+        let mut service = TraceLayer::new().layer(Greeter(Code::Ok));
+
+        drop(call(&mut service));
+
+        let spans = pipeline.spans();
+        assert_eq!(spans.len(), 1);
+        assert!(
+            !spans[0]
+                .attributes
+                .iter()
+                .any(|kv| kv.key.as_str() == "rpc.grpc.status_code")
+        );
+    }
+}
@@ -7,13 +7,28 @@
 //! A Rust library for distributed tracing using OpenTelemetry.
 //!
 //! This crate provides utilities to configure and use OpenTelemetry tracing in Rust applications,
-//! with support for multiple exporters (OTLP gRPC and stdout) and propagation of trace context
+//! with support for multiple exporters (OTLP gRPC, OTLP HTTP, stdout and file) and propagation of trace context
 //! across service boundaries, particularly focused on gRPC communication.
 //!
 //! ## Features
 //!
 //! - `otlp`: Enables the OpenTelemetry Protocol (OTLP) exporter over gRPC
+//! - `otlp-http`: Enables the OpenTelemetry Protocol (OTLP) exporter over HTTP/protobuf
 //! - `stdout`: Enables console output for traces, useful for development
+//! - `file`: Enables the line-delimited JSON file exporter, useful for offline capture
+//! - `testing`: Enables the in-memory exporter to assert the produced spans in tests
+//! - `b3`: Enables the Zipkin B3 propagation formats
+//! - `jaeger`: Enables the Jaeger propagation format
+//! - `xray`: Enables the AWS X-Ray propagation format and ID generator
+//! - `datadog`: Enables the Datadog propagation format
+//! - `tracing-layer`: Enables a `tracing-subscriber` layer bridging `tracing` spans to OpenTelemetry
+//! - `tracing-events`: Enables a `tracing-subscriber` layer recording `tracing` events as span events
+//! - `http`: Enables trace context propagation through `http` crate headers
+//! - `axum`: Enables a tower layer creating a server span per axum request
+//! - `reqwest`: Enables trace context propagation into outgoing `reqwest` requests
+//! - `kafka`: Enables trace context propagation through Kafka record headers
+//! - `nats`: Enables trace context propagation through NATS message headers
+//! - `channel`: Enables helpers to propagate trace context across tokio mpsc channels
 //!
 //! ## Usage
 //!
@@ -28,9 +43,18 @@
 //! }
 //! ```
 
+pub mod channel;
+pub mod config;
 pub mod errors;
+#[cfg(feature = "tracing-events")]
+pub mod events;
 pub mod exporters;
 pub mod extractors;
 pub mod helpers;
 pub mod injectors;
+pub mod integration;
+pub mod processors;
+pub mod propagation;
 pub mod provider;
+#[cfg(test)]
+mod test_utils;
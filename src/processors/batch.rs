@@ -0,0 +1,224 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Batch span processor tuning.
+//!
+//! This module builds the batch span processor used by the OTLP and file exporters from the
+//! tuning values in `TracesConfigs`, keeping the SDK defaults for unset values.
+
+#[cfg(any(feature = "otlp", feature = "otlp-http"))]
+use crate::processors::{self, ExportMode};
+use crate::{
+    config::TracesConfigs,
+    errors::TracesError,
+    exporters::logging::LoggingExporter,
+    processors::stats::{CountingExporter, CountingProcessor, QueueCounter},
+};
+use opentelemetry_sdk::trace::{BatchConfig, BatchConfigBuilder, BatchSpanProcessor, SpanExporter};
+#[cfg(any(feature = "otlp", feature = "otlp-http"))]
+use opentelemetry_sdk::trace::{SimpleSpanProcessor, TracerProviderBuilder};
+use tracing::error;
+
+/// SDK default for the maximum queue size.
+const DEFAULT_MAX_QUEUE_SIZE: usize = 2048;
+
+/// SDK default for the maximum export batch size.
+const DEFAULT_MAX_EXPORT_BATCH_SIZE: usize = 512;
+
+/// Builds the batch configuration from the tuning values.
+///
+/// # Arguments
+///
+/// * `cfgs` - Tracing configuration holding the batch tuning values
+///
+/// # Returns
+///
+/// * `Ok(BatchConfig)` if the values are consistent
+/// * `Err(TracesError::InvalidConfigError)` if the export batch size exceeds the queue size
+pub(crate) fn batch_config(cfgs: &TracesConfigs) -> Result<BatchConfig, TracesError> {
+    let max_queue_size = cfgs.max_queue_size.unwrap_or(DEFAULT_MAX_QUEUE_SIZE);
+    let max_export_batch_size = cfgs
+        .max_export_batch_size
+        .unwrap_or(DEFAULT_MAX_EXPORT_BATCH_SIZE);
+
+    if max_export_batch_size > max_queue_size {
+        error!(
+            max_export_batch_size = max_export_batch_size,
+            max_queue_size = max_queue_size,
+            "max export batch size exceeds max queue size"
+        );
+        return Err(TracesError::InvalidConfigError(format!(
+            "max_export_batch_size ({max_export_batch_size}) exceeds max_queue_size ({max_queue_size})"
+        )));
+    }
+
+    let mut builder = BatchConfigBuilder::default();
+
+    if let Some(max_queue_size) = cfgs.max_queue_size {
+        builder = builder.with_max_queue_size(max_queue_size);
+    }
+
+    if let Some(scheduled_delay) = cfgs.scheduled_delay {
+        builder = builder.with_scheduled_delay(scheduled_delay);
+    }
+
+    if let Some(max_export_batch_size) = cfgs.max_export_batch_size {
+        builder = builder.with_max_export_batch_size(max_export_batch_size);
+    }
+
+    Ok(builder.build())
+}
+
+/// Wraps an exporter to update the pipeline counters and log the export failures.
+fn exporter_with_stats<E: SpanExporter>(
+    exporter: E,
+    queue: &QueueCounter,
+    cfgs: &TracesConfigs,
+) -> CountingExporter<LoggingExporter<E>> {
+    CountingExporter::new(
+        LoggingExporter::new(exporter, cfgs.export_error_log_interval),
+        queue.clone(),
+    )
+}
+
+/// Builds the batch span processor for an exporter.
+///
+/// The exporter and the processor are wrapped to update the pipeline counters read with
+/// `provider::stats`, the queue bound being enforced by the `CountingProcessor`, and the
+/// exporter to log the export failures, see `LoggingExporter`.
+///
+/// # Arguments
+///
+/// * `exporter` - The span exporter
+/// * `cfgs` - Tracing configuration holding the batch tuning values
+///
+/// # Returns
+///
+/// * `Ok(CountingProcessor<BatchSpanProcessor>)` if the tuning values are consistent
+/// * `Err(TracesError::InvalidConfigError)` otherwise
+pub(crate) fn batch_processor<E: SpanExporter + 'static>(
+    exporter: E,
+    cfgs: &TracesConfigs,
+) -> Result<CountingProcessor<BatchSpanProcessor>, TracesError> {
+    let queue = QueueCounter::default();
+    let processor = BatchSpanProcessor::builder(exporter_with_stats(exporter, &queue, cfgs))
+        .with_batch_config(batch_config(cfgs)?)
+        .build();
+
+    Ok(CountingProcessor::new(
+        processor,
+        queue,
+        Some(cfgs.max_queue_size.unwrap_or(DEFAULT_MAX_QUEUE_SIZE)),
+    ))
+}
+
+/// Registers the exporting processor of the configured export mode.
+///
+/// The batch processor is used by default; `ExportMode::Simple` exports each span when it
+/// ends, so no flush is needed before reading the exported spans.
+///
+/// # Arguments
+///
+/// * `builder` - The tracer provider builder
+/// * `exporter` - The span exporter
+/// * `cfgs` - Tracing configuration holding the export mode and the batch tuning values
+///
+/// # Returns
+///
+/// * `Ok(TracerProviderBuilder)` with the exporting processor and its wrappers registered
+/// * `Err(TracesError::InvalidConfigError)` if the batch tuning values are inconsistent
+#[cfg(any(feature = "otlp", feature = "otlp-http"))]
+pub(crate) fn with_exporter<E: SpanExporter + 'static>(
+    builder: TracerProviderBuilder,
+    exporter: E,
+    cfgs: &TracesConfigs,
+) -> Result<TracerProviderBuilder, TracesError> {
+    let builder = match cfgs.export_mode {
+        ExportMode::Batch => {
+            processors::with_exporting_processor(builder, batch_processor(exporter, cfgs)?, cfgs)
+        }
+        ExportMode::Simple => {
+            let queue = QueueCounter::default();
+            let processor = SimpleSpanProcessor::new(exporter_with_stats(exporter, &queue, cfgs));
+
+            processors::with_exporting_processor(
+                builder,
+                CountingProcessor::new(processor, queue, None),
+                cfgs,
+            )
+        }
+    };
+
+    Ok(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "otlp", feature = "otlp-http"))]
+    use opentelemetry::trace::{Tracer, TracerProvider};
+    #[cfg(any(feature = "otlp", feature = "otlp-http"))]
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+    #[cfg(any(feature = "otlp", feature = "otlp-http"))]
+    use std::time::Duration;
+
+    /// Ends a span on a provider exporting with the configured export mode.
+    ///
+    /// # Returns
+    ///
+    /// The provider and the exporter receiving its spans
+    #[cfg(any(feature = "otlp", feature = "otlp-http"))]
+    fn export_one(cfgs: &TracesConfigs) -> (SdkTracerProvider, InMemorySpanExporter) {
+        let exporter = InMemorySpanExporter::default();
+        let provider = with_exporter(SdkTracerProvider::builder(), exporter.clone(), cfgs)
+            .expect("consistent batch config")
+            .build();
+        provider.tracer("test").in_span("exported", |_| {});
+
+        (provider, exporter)
+    }
+
+    #[cfg(any(feature = "otlp", feature = "otlp-http"))]
+    #[test]
+    fn simple_mode_exports_each_span_without_flush() {
+        let cfgs = TracesConfigs {
+            export_mode: ExportMode::Simple,
+            ..TracesConfigs::default()
+        };
+
+        let (_provider, exporter) = export_one(&cfgs);
+
+        assert_eq!(exporter.get_finished_spans().expect("spans").len(), 1);
+    }
+
+    #[cfg(any(feature = "otlp", feature = "otlp-http"))]
+    #[test]
+    fn batch_mode_exports_the_spans_on_flush() {
+        let cfgs = TracesConfigs {
+            export_mode: ExportMode::Batch,
+            scheduled_delay: Some(Duration::from_secs(3600)),
+            ..TracesConfigs::default()
+        };
+
+        let (provider, exporter) = export_one(&cfgs);
+        assert!(exporter.get_finished_spans().expect("spans").is_empty());
+
+        provider.force_flush().expect("flushed");
+        assert_eq!(exporter.get_finished_spans().expect("spans").len(), 1);
+    }
+
+    #[test]
+    fn rejects_an_export_batch_larger_than_the_queue() {
+        let cfgs = TracesConfigs {
+            max_queue_size: Some(64),
+            max_export_batch_size: Some(128),
+            ..TracesConfigs::default()
+        };
+
+        assert!(matches!(
+            batch_config(&cfgs),
+            Err(TracesError::InvalidConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Flush timer processor.
+//!
+//! This module provides a span processor wrapper that flushes the wrapped processor
+//! on a fixed interval, independently of its own export schedule. It keeps low-volume
+//! producers, such as cron jobs, from holding finished spans for a long time.
+
+use opentelemetry::Context;
+use opentelemetry_sdk::{
+    Resource,
+    error::{OTelSdkError, OTelSdkResult},
+    trace::{Span, SpanData, SpanProcessor},
+};
+use std::{
+    sync::{
+        Arc, Mutex, Once,
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tracing::warn;
+
+/// A span processor wrapper that periodically flushes the wrapped processor.
+///
+/// The timer starts with the first span, so the wrapped processor is configured
+/// (e.g. with the provider resource) before it is shared with the timer.
+#[derive(Debug)]
+pub struct FlushTimerProcessor<P> {
+    inner: Arc<P>,
+    interval: Duration,
+    started: Once,
+    stop: Mutex<Option<mpsc::Sender<()>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<P: SpanProcessor + 'static> FlushTimerProcessor<P> {
+    /// Creates a new `FlushTimerProcessor` wrapping the provided processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The processor to flush, usually a batch span processor
+    /// * `interval` - The interval between two flushes
+    ///
+    /// # Returns
+    ///
+    /// A new `FlushTimerProcessor` instance
+    pub fn new(inner: P, interval: Duration) -> FlushTimerProcessor<P> {
+        FlushTimerProcessor {
+            inner: Arc::new(inner),
+            interval,
+            started: Once::new(),
+            stop: Mutex::new(None),
+            worker: Mutex::new(None),
+        }
+    }
+
+    /// Starts the flush timer.
+    fn start(&self) {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let inner = self.inner.clone();
+        let interval = self.interval;
+
+        let worker = thread::Builder::new()
+            .name("traces-flush-timer".into())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    if let Err(err) = inner.force_flush() {
+                        warn!(error = err.to_string(), "failure to flush spans on timer");
+                    }
+                }
+            })
+            .ok();
+
+        if let (Ok(mut s), Ok(mut w)) = (self.stop.lock(), self.worker.lock()) {
+            *s = Some(stop);
+            *w = worker;
+        }
+    }
+}
+
+impl<P: SpanProcessor + 'static> SpanProcessor for FlushTimerProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.started.call_once(|| self.start());
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        self.inner.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        // Prevent a timer from starting after shutdown
+        self.started.call_once(|| {});
+
+        let stop = self
+            .stop
+            .lock()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?
+            .take();
+        drop(stop);
+
+        let worker = self
+            .worker
+            .lock()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?
+            .take();
+
+        if let Some(worker) = worker {
+            worker
+                .join()
+                .map_err(|_| OTelSdkError::InternalFailure("flush timer worker panicked".into()))?;
+        }
+
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        // The timer is not running yet, so the processor is not shared
+        if let Some(inner) = Arc::get_mut(&mut self.inner) {
+            inner.set_resource(resource);
+        }
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Span processors.
+//!
+//! This module contains span processors that complement or wrap the exporting processor
+//! configured by each exporter. Which processors are registered is driven by
+//! `TracesConfigs`.
+//!
+//! Every processor registered on a provider receives every finished span, so processors
+//! that filter spans are wrappers around the exporting processor rather than siblings.
+
+#[cfg(any(feature = "otlp", feature = "otlp-http", feature = "file"))]
+pub(crate) mod batch;
+pub mod flush_timer;
+pub mod span_filter;
+pub mod span_kind_attributes;
+pub mod span_limit;
+pub mod span_metrics;
+pub mod stats;
+pub mod trace_buffer;
+
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+use crate::config::TracesConfigs;
+use crate::errors::TracesError;
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+use opentelemetry::Context;
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanProcessor, TracerProviderBuilder},
+};
+use std::str::FromStr;
+#[cfg(feature = "otlp")]
+use std::sync::Arc;
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+use std::time::Duration;
+
+/// How the OTLP exporters hand finished spans to the exporter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportMode {
+    /// Spans are queued and exported in batches in the background, for production.
+    #[default]
+    Batch,
+    /// Each span is exported synchronously when it ends, for tests and short scripts.
+    Simple,
+}
+
+impl FromStr for ExportMode {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "batch" => Ok(ExportMode::Batch),
+            "simple" => Ok(ExportMode::Simple),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// A type-erased span processor, used to compose the processor wrappers.
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+#[derive(Debug)]
+struct BoxedProcessor(Box<dyn SpanProcessor>);
+
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+impl BoxedProcessor {
+    fn new<P: SpanProcessor + 'static>(processor: P) -> BoxedProcessor {
+        BoxedProcessor(Box::new(processor))
+    }
+}
+
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+impl SpanProcessor for BoxedProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.0.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        self.0.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.0.set_resource(resource);
+    }
+}
+
+/// A span processor shared between the providers built from the same configuration.
+///
+/// The SDK only hands the resource to processors it owns exclusively, so the shared
+/// processor does not receive `set_resource`.
+#[cfg(feature = "otlp")]
+#[derive(Debug, Clone)]
+pub(crate) struct SharedProcessor(pub(crate) Arc<dyn SpanProcessor>);
+
+#[cfg(feature = "otlp")]
+impl SpanProcessor for SharedProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.0.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        self.0.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+}
+
+/// Registers the exporting processor, wrapped by the processors enabled in the configuration.
+///
+/// # Arguments
+///
+/// * `builder` - The tracer provider builder
+/// * `processor` - The processor exporting the spans
+/// * `cfgs` - Tracing configuration selecting the wrappers
+///
+/// # Returns
+///
+/// The builder with the exporting processor registered
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+pub(crate) fn with_exporting_processor<P: SpanProcessor + 'static>(
+    builder: TracerProviderBuilder,
+    processor: P,
+    cfgs: &TracesConfigs,
+) -> TracerProviderBuilder {
+    let mut processor = BoxedProcessor::new(processor);
+
+    if !cfgs.span_kind_attributes.is_empty() {
+        processor = BoxedProcessor::new(span_kind_attributes::SpanKindAttributesProcessor::new(
+            processor,
+            cfgs.span_kind_attributes.clone(),
+        ));
+    }
+
+    if let Some(limit) = cfgs.max_spans_per_trace {
+        processor = BoxedProcessor::new(span_limit::SpanLimitProcessor::new(processor, limit));
+    }
+
+    if !cfgs.excluded_spans.is_empty() {
+        processor = BoxedProcessor::new(span_filter::SpanFilterProcessor::new(
+            processor,
+            cfgs.excluded_spans.clone(),
+        ));
+    }
+
+    if let Some(interval) = cfgs.flush_interval {
+        processor = BoxedProcessor::new(flush_timer::FlushTimerProcessor::new(processor, interval));
+    }
+
+    // Outermost, so the flush timer only flushes spans of completed traces
+    if cfgs.batch_by_trace {
+        processor = BoxedProcessor::new(trace_buffer::TraceBufferProcessor::new(
+            processor,
+            cfgs.trace_buffer_timeout,
+        ));
+    }
+
+    builder.with_span_processor(processor)
+}
+
+/// Registers the span processors enabled in the configuration.
+///
+/// # Arguments
+///
+/// * `builder` - The tracer provider builder
+/// * `cfgs` - Tracing configuration selecting the processors
+///
+/// # Returns
+///
+/// The builder with the enabled processors registered
+#[cfg(any(
+    feature = "stdout",
+    feature = "otlp",
+    feature = "otlp-http",
+    feature = "file",
+    feature = "testing"
+))]
+pub(crate) fn with_processors(
+    mut builder: TracerProviderBuilder,
+    cfgs: &TracesConfigs,
+) -> TracerProviderBuilder {
+    if cfgs.span_metrics_enabled {
+        builder = builder.with_span_processor(span_metrics::SpanMetricsProcessor::new(
+            cfgs.span_metrics_interval,
+        ));
+    }
+
+    builder
+}
+
+#[cfg(all(test, feature = "otlp"))]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use opentelemetry::{
+        KeyValue, Value,
+        trace::{Span as _, Tracer},
+    };
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    /// A processor redacting the `user.email` attribute when the span starts.
+    #[derive(Debug)]
+    struct RedactEmail;
+
+    impl SpanProcessor for RedactEmail {
+        fn on_start(&self, span: &mut Span, _cx: &Context) {
+            span.set_attribute(KeyValue::new("user.email", "[redacted]"));
+        }
+
+        fn on_end(&self, _span: SpanData) {}
+
+        fn force_flush(&self) -> OTelSdkResult {
+            Ok(())
+        }
+
+        fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn custom_processors_change_the_exported_attributes() {
+        // Registered before the exporting processor, as by `otlp_grpc::Builder`
+        let pipeline = test_utils::pipeline_with(
+            SdkTracerProvider::builder()
+                .with_span_processor(SharedProcessor(Arc::new(RedactEmail))),
+        );
+
+        let mut span = pipeline
+            .tracer
+            .span_builder("login")
+            .with_attributes([KeyValue::new("user.email", "jane@example.com")])
+            .start(&pipeline.tracer);
+        span.end();
+
+        let exported = pipeline.spans().pop().expect("exported span");
+        // The SDK appends the new value, which backends read as the attribute value
+        let email = exported
+            .attributes
+            .iter()
+            .rev()
+            .find(|kv| kv.key.as_str() == "user.email")
+            .map(|kv| kv.value.clone());
+        assert_eq!(email, Some(Value::from("[redacted]")));
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Span filter processor.
+//!
+//! This module provides a span processor wrapper that drops spans whose name matches one
+//! of a set of glob patterns, e.g. to keep health-check endpoints out of the exported data.
+
+use opentelemetry::Context;
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanProcessor},
+};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// A span processor wrapper that drops the spans whose name matches an excluded pattern.
+///
+/// Patterns are globs matched against the whole span name, where `*` matches any sequence
+/// of characters and `?` matches a single character, e.g. `GET /health*` or `*readiness*`.
+/// Only the matching spans are dropped, their children are still forwarded.
+#[derive(Debug)]
+pub struct SpanFilterProcessor<P> {
+    inner: P,
+    excluded: Vec<String>,
+    dropped: AtomicU64,
+}
+
+impl<P: SpanProcessor> SpanFilterProcessor<P> {
+    /// Creates a new `SpanFilterProcessor` wrapping the provided processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The processor receiving the spans not excluded
+    /// * `excluded` - The glob patterns of the span names to drop
+    ///
+    /// # Returns
+    ///
+    /// A new `SpanFilterProcessor` instance
+    pub fn new(inner: P, excluded: Vec<String>) -> SpanFilterProcessor<P> {
+        SpanFilterProcessor {
+            inner,
+            excluded,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of spans dropped since creation.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns whether a span name matches an excluded pattern.
+    fn is_excluded(&self, name: &str) -> bool {
+        self.excluded
+            .iter()
+            .any(|pattern| glob_match(pattern.as_bytes(), name.as_bytes()))
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for SpanFilterProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if self.is_excluded(&span.name) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.inner.on_end(span);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// Matches a name against a glob pattern supporting `*` and `?`.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` absorb one more character
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
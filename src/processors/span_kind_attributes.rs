@@ -0,0 +1,183 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Span kind attributes processor.
+//!
+//! This module provides a span processor wrapper that adds default attributes to spans
+//! depending on their kind, e.g. `peer.service` on every client span.
+
+use opentelemetry::{
+    Context, KeyValue,
+    trace::{Span as _, SpanKind},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanProcessor},
+};
+use std::{collections::HashMap, time::Duration};
+
+/// A span processor wrapper that adds default attributes to the spans of a kind.
+///
+/// The defaults are set when the span starts, for the keys the span was not created with, so
+/// they count towards the span attribute limit and are seen by the wrapped processor's
+/// `on_start`. An attribute set explicitly on the span, at creation or later with
+/// `helpers::set_attribute`, takes precedence: the default is removed when the span ends.
+#[derive(Debug)]
+pub struct SpanKindAttributesProcessor<P> {
+    inner: P,
+    attributes: Vec<(SpanKind, Vec<KeyValue>)>,
+}
+
+impl<P: SpanProcessor> SpanKindAttributesProcessor<P> {
+    /// Creates a new `SpanKindAttributesProcessor` wrapping the provided processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The processor receiving the spans
+    /// * `attributes` - The default attributes of each span kind
+    ///
+    /// # Returns
+    ///
+    /// A new `SpanKindAttributesProcessor` instance
+    pub fn new(
+        inner: P,
+        attributes: Vec<(SpanKind, HashMap<String, String>)>,
+    ) -> SpanKindAttributesProcessor<P> {
+        SpanKindAttributesProcessor {
+            inner,
+            attributes: attributes
+                .into_iter()
+                .map(|(kind, attributes)| {
+                    let attributes = attributes
+                        .into_iter()
+                        .map(|(key, value)| KeyValue::new(key, value))
+                        .collect();
+                    (kind, attributes)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<P> SpanKindAttributesProcessor<P> {
+    /// Returns the default attributes of a span kind.
+    fn defaults(&self, kind: &SpanKind) -> impl Iterator<Item = &KeyValue> {
+        self.attributes
+            .iter()
+            .filter(move |(attributes_kind, _)| attributes_kind == kind)
+            .flat_map(|(_, attributes)| attributes)
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for SpanKindAttributesProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        // Non-recording spans have no data and are never exported
+        if let Some(data) = span.exported_data() {
+            for default in self.defaults(&data.span_kind) {
+                if !data.attributes.iter().any(|kv| kv.key == default.key) {
+                    span.set_attribute(default.clone());
+                }
+            }
+        }
+
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, mut span: SpanData) {
+        // A default overridden after the span started is kept alongside the explicit value
+        for default in self.defaults(&span.span_kind) {
+            let set = span.attributes.iter().filter(|kv| kv.key == default.key);
+            if set.count() > 1
+                && let Some(index) = span.attributes.iter().position(|kv| kv == default)
+            {
+                span.attributes.remove(index);
+            }
+        }
+
+        self.inner.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers;
+    use opentelemetry::{global::BoxedTracer, trace::TraceContextExt, trace::TracerProvider};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SimpleSpanProcessor};
+
+    fn defaults() -> Vec<(SpanKind, HashMap<String, String>)> {
+        vec![
+            (
+                SpanKind::Client,
+                HashMap::from([("peer.service".to_owned(), "payments".to_owned())]),
+            ),
+            (
+                SpanKind::Server,
+                HashMap::from([("server.address".to_owned(), "api.local".to_owned())]),
+            ),
+        ]
+    }
+
+    fn provider(exporter: &InMemorySpanExporter) -> (SdkTracerProvider, BoxedTracer) {
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(SpanKindAttributesProcessor::new(
+                SimpleSpanProcessor::new(exporter.clone()),
+                defaults(),
+            ))
+            .build();
+        let tracer = BoxedTracer::new(Box::new(provider.tracer("test")));
+
+        (provider, tracer)
+    }
+
+    #[test]
+    fn sets_the_defaults_of_the_span_kind() {
+        let exporter = InMemorySpanExporter::default();
+        let (_provider, tracer) = provider(&exporter);
+
+        let server = helpers::ctx(&tracer, SpanKind::Server, "handle");
+        let client = helpers::child(&tracer, &server, SpanKind::Client, "charge");
+        client.span().end();
+        server.span().end();
+
+        let spans = exporter.get_finished_spans().expect("spans");
+        assert_eq!(
+            spans[0].attributes,
+            vec![KeyValue::new("peer.service", "payments")]
+        );
+        assert_eq!(
+            spans[1].attributes,
+            vec![KeyValue::new("server.address", "api.local")]
+        );
+    }
+
+    #[test]
+    fn explicit_attributes_override_the_defaults() {
+        let exporter = InMemorySpanExporter::default();
+        let (_provider, tracer) = provider(&exporter);
+
+        let client = helpers::ctx(&tracer, SpanKind::Client, "charge");
+        helpers::set_attribute(&client, "peer.service", "billing");
+        client.span().end();
+
+        let spans = exporter.get_finished_spans().expect("spans");
+        assert_eq!(
+            spans[0].attributes,
+            vec![KeyValue::new("peer.service", "billing")]
+        );
+    }
+}
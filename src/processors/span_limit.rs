@@ -0,0 +1,239 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Span limit processor.
+//!
+//! This module provides a span processor wrapper that caps the number of spans exported
+//! per trace, protecting the backend from pathological traces such as runaway loops.
+
+use opentelemetry::{
+    Context,
+    trace::{SpanId, TraceId},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanProcessor},
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tracing::warn;
+
+/// Maximum number of traces tracked at once, bounding the memory used for counting.
+const MAX_TRACKED_TRACES: usize = 65_536;
+
+/// A span processor wrapper that drops the spans of a trace beyond a limit.
+///
+/// Spans are counted per trace as they end. Once a trace reaches the limit, its remaining
+/// spans are dropped and counted instead of being forwarded to the wrapped processor.
+/// A trace stops being tracked when its local root span ends, i.e. a span without parent or
+/// continuing a remote trace; if more than 65,536 traces are tracked at once, the counts are
+/// reset.
+#[derive(Debug)]
+pub struct SpanLimitProcessor<P> {
+    inner: P,
+    max_spans_per_trace: u64,
+    spans: Mutex<HashMap<TraceId, u64>>,
+    max_tracked_traces: usize,
+    dropped: AtomicU64,
+}
+
+impl<P: SpanProcessor> SpanLimitProcessor<P> {
+    /// Creates a new `SpanLimitProcessor` wrapping the provided processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The processor receiving the spans within the limit
+    /// * `max_spans_per_trace` - The maximum number of spans forwarded per trace
+    ///
+    /// # Returns
+    ///
+    /// A new `SpanLimitProcessor` instance
+    pub fn new(inner: P, max_spans_per_trace: u64) -> SpanLimitProcessor<P> {
+        SpanLimitProcessor {
+            inner,
+            max_spans_per_trace,
+            spans: Mutex::new(HashMap::new()),
+            max_tracked_traces: MAX_TRACKED_TRACES,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of spans dropped since creation.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Counts a finished span and returns whether it is within the limit.
+    fn admit(&self, span: &SpanData) -> bool {
+        let Ok(mut spans) = self.spans.lock() else {
+            return true;
+        };
+
+        let trace_id = span.span_context.trace_id();
+        let is_root = span.parent_span_id == SpanId::INVALID || span.parent_span_is_remote;
+
+        if !spans.contains_key(&trace_id) && spans.len() >= self.max_tracked_traces {
+            spans.clear();
+        }
+
+        let count = spans.entry(trace_id).or_insert(0);
+        *count += 1;
+        let count = *count;
+
+        if is_root {
+            spans.remove(&trace_id);
+        }
+
+        if count <= self.max_spans_per_trace {
+            return true;
+        }
+
+        if count == self.max_spans_per_trace + 1 {
+            warn!(
+                trace_id = trace_id.to_string(),
+                limit = self.max_spans_per_trace,
+                "trace exceeded the span limit, dropping spans"
+            );
+        }
+
+        false
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for SpanLimitProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if self.admit(&span) {
+            self.inner.on_end(span);
+        } else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{helpers, test_utils};
+    use opentelemetry::trace::{SpanContext, SpanKind, TraceContextExt, TraceFlags, TraceState};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SimpleSpanProcessor};
+
+    /// Builds a processor forwarding the spans within the limit to an in-memory exporter.
+    fn processor(
+        max_spans_per_trace: u64,
+    ) -> (
+        SpanLimitProcessor<SimpleSpanProcessor<InMemorySpanExporter>>,
+        InMemorySpanExporter,
+    ) {
+        let exporter = InMemorySpanExporter::default();
+        let processor = SpanLimitProcessor::new(
+            SimpleSpanProcessor::new(exporter.clone()),
+            max_spans_per_trace,
+        );
+
+        (processor, exporter)
+    }
+
+    /// Ends `children` child spans then their root, returning the finished spans in end order.
+    fn trace(parent: &opentelemetry::Context, children: usize) -> Vec<SpanData> {
+        let pipeline = test_utils::pipeline();
+        let root = helpers::child(&pipeline.tracer, parent, SpanKind::Server, "root");
+        for _ in 0..children {
+            helpers::child(&pipeline.tracer, &root, SpanKind::Internal, "child")
+                .span()
+                .end();
+        }
+        root.span().end();
+
+        pipeline.spans()
+    }
+
+    fn tracked(processor: &SpanLimitProcessor<SimpleSpanProcessor<InMemorySpanExporter>>) -> usize {
+        processor.spans.lock().unwrap().len()
+    }
+
+    #[test]
+    fn drops_the_spans_beyond_the_limit() {
+        let (processor, exporter) = processor(2);
+
+        for span in trace(&opentelemetry::Context::new(), 4) {
+            processor.on_end(span);
+        }
+
+        assert_eq!(exporter.get_finished_spans().unwrap().len(), 2);
+        assert_eq!(processor.dropped(), 3);
+    }
+
+    #[test]
+    fn stops_tracking_a_trace_when_its_root_ends() {
+        let (processor, _exporter) = processor(10);
+        let mut spans = trace(&opentelemetry::Context::new(), 2);
+        let root = spans.pop().expect("root span");
+
+        for span in spans {
+            processor.on_end(span);
+        }
+        assert_eq!(tracked(&processor), 1);
+
+        processor.on_end(root);
+        assert_eq!(tracked(&processor), 0);
+    }
+
+    #[test]
+    fn stops_tracking_a_remote_trace_when_its_local_root_ends() {
+        let (processor, _exporter) = processor(10);
+        let remote = opentelemetry::Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from(1_u128),
+            SpanId::from(1_u64),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        ));
+
+        for span in trace(&remote, 2) {
+            processor.on_end(span);
+        }
+
+        assert_eq!(tracked(&processor), 0);
+    }
+
+    #[test]
+    fn resets_the_counts_beyond_the_tracked_traces() {
+        let (mut processor, _exporter) = processor(10);
+        processor.max_tracked_traces = 2;
+
+        for _ in 0..3 {
+            let mut spans = trace(&opentelemetry::Context::new(), 1);
+            // The root span never ends, so the trace stays tracked
+            spans.pop();
+            for span in spans {
+                processor.on_end(span);
+            }
+        }
+
+        assert_eq!(tracked(&processor), 1);
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Span metrics processor.
+//!
+//! This module provides a span processor that counts finished spans by status and kind
+//! and logs a summary at a fixed interval, giving cheap rate and error signals without
+//! the full metrics SDK.
+
+use opentelemetry::{
+    Context,
+    trace::{SpanKind, Status},
+};
+use opentelemetry_sdk::{
+    error::{OTelSdkError, OTelSdkResult},
+    trace::{Span, SpanData, SpanProcessor},
+};
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tracing::info;
+
+/// Span counters accumulated between two summaries.
+#[derive(Debug, Default)]
+struct SpanCounters {
+    unset: AtomicU64,
+    ok: AtomicU64,
+    error: AtomicU64,
+    internal: AtomicU64,
+    server: AtomicU64,
+    client: AtomicU64,
+    producer: AtomicU64,
+    consumer: AtomicU64,
+}
+
+impl SpanCounters {
+    /// Tallies a finished span.
+    fn record(&self, span: &SpanData) {
+        let status = match span.status {
+            Status::Unset => &self.unset,
+            Status::Ok => &self.ok,
+            Status::Error { .. } => &self.error,
+        };
+        status.fetch_add(1, Ordering::Relaxed);
+
+        let kind = match span.span_kind {
+            SpanKind::Internal => &self.internal,
+            SpanKind::Server => &self.server,
+            SpanKind::Client => &self.client,
+            SpanKind::Producer => &self.producer,
+            SpanKind::Consumer => &self.consumer,
+        };
+        kind.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Logs the accumulated counters and resets them.
+    fn report(&self) {
+        info!(
+            unset = self.unset.swap(0, Ordering::Relaxed),
+            ok = self.ok.swap(0, Ordering::Relaxed),
+            error = self.error.swap(0, Ordering::Relaxed),
+            internal = self.internal.swap(0, Ordering::Relaxed),
+            server = self.server.swap(0, Ordering::Relaxed),
+            client = self.client.swap(0, Ordering::Relaxed),
+            producer = self.producer.swap(0, Ordering::Relaxed),
+            consumer = self.consumer.swap(0, Ordering::Relaxed),
+            "traces::span_metrics summary"
+        );
+    }
+}
+
+/// A span processor that periodically logs span counts by status and kind.
+///
+/// The counters are reset after each summary, so every summary covers the spans
+/// finished since the previous one. A final summary is logged on shutdown.
+#[derive(Debug)]
+pub struct SpanMetricsProcessor {
+    counters: Arc<SpanCounters>,
+    stop: Mutex<Option<mpsc::Sender<()>>>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl SpanMetricsProcessor {
+    /// Creates a new `SpanMetricsProcessor` and starts its reporting timer.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval between two summaries
+    ///
+    /// # Returns
+    ///
+    /// A new `SpanMetricsProcessor` instance
+    pub fn new(interval: Duration) -> SpanMetricsProcessor {
+        let counters = Arc::new(SpanCounters::default());
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let worker_counters = counters.clone();
+        let worker = thread::Builder::new()
+            .name("traces-span-metrics".into())
+            .spawn(move || {
+                loop {
+                    match stopped.recv_timeout(interval) {
+                        Err(RecvTimeoutError::Timeout) => worker_counters.report(),
+                        _ => {
+                            worker_counters.report();
+                            break;
+                        }
+                    }
+                }
+            })
+            .ok();
+
+        SpanMetricsProcessor {
+            counters,
+            stop: Mutex::new(Some(stop)),
+            worker: Mutex::new(worker),
+        }
+    }
+}
+
+impl SpanProcessor for SpanMetricsProcessor {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+    fn on_end(&self, span: SpanData) {
+        self.counters.record(&span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.counters.report();
+        Ok(())
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        let stop = self
+            .stop
+            .lock()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?
+            .take();
+
+        // Dropping the sender wakes the worker, which logs a final summary
+        let Some(stop) = stop else {
+            return Err(OTelSdkError::AlreadyShutdown);
+        };
+        drop(stop);
+
+        let worker = self
+            .worker
+            .lock()
+            .map_err(|err| OTelSdkError::InternalFailure(err.to_string()))?
+            .take();
+
+        if let Some(worker) = worker {
+            worker.join().map_err(|_| {
+                OTelSdkError::InternalFailure("span metrics worker panicked".into())
+            })?;
+        }
+
+        Ok(())
+    }
+}
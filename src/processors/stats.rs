@@ -0,0 +1,389 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Span pipeline counters.
+//!
+//! This module provides the exporter and processor wrappers counting the spans exported,
+//! dropped and failed by the exporting pipeline, read with `provider::stats`.
+//!
+//! The batch processor of the SDK drops spans silently when its queue is full, so the queue
+//! bound is enforced by `CountingProcessor` instead: spans are counted as queued when handed
+//! to the wrapped processor and released by `CountingExporter` once exported or failed, and
+//! spans arriving while the queue is full are dropped and counted before reaching the SDK.
+//!
+//! The queued spans are counted per pipeline, in a `QueueCounter` shared by the processor
+//! and the exporter of the pipeline, so providers built side by side, or a provider replaced
+//! on reinstall, do not share their queue bound. Only the totals read by `provider::stats`
+//! are process-wide.
+
+use crate::provider::Stats;
+use opentelemetry::Context;
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanExporter, SpanProcessor},
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+/// Spans exported successfully since the process started.
+static EXPORTED: AtomicU64 = AtomicU64::new(0);
+
+/// Spans dropped because the export queue was full since the process started.
+static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// Spans of the failed export requests since the process started.
+static EXPORT_ERRORS: AtomicU64 = AtomicU64::new(0);
+
+/// Spans queued for export and not yet exported or failed, across all pipelines.
+static QUEUED: AtomicU64 = AtomicU64::new(0);
+
+/// Returns a snapshot of the span pipeline counters.
+pub(crate) fn snapshot() -> Stats {
+    Stats {
+        exported: EXPORTED.load(Ordering::Relaxed),
+        dropped: DROPPED.load(Ordering::Relaxed),
+        export_errors: EXPORT_ERRORS.load(Ordering::Relaxed),
+        queued: QUEUED.load(Ordering::Relaxed),
+    }
+}
+
+/// The number of spans queued in one exporting pipeline.
+///
+/// The counter is shared by the `CountingProcessor` and the `CountingExporter` of the
+/// pipeline; clones refer to the same counter.
+#[derive(Debug, Clone, Default)]
+pub struct QueueCounter(Arc<AtomicU64>);
+
+impl QueueCounter {
+    /// Returns the number of spans queued and not yet exported or failed.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Counts a span as queued unless `max` spans are already queued.
+    ///
+    /// Returns whether the span was counted.
+    fn enqueue(&self, max: Option<u64>) -> bool {
+        let enqueued = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| match max {
+                Some(max) if queued >= max => None,
+                _ => Some(queued + 1),
+            })
+            .is_ok();
+
+        if enqueued {
+            QUEUED.fetch_add(1, Ordering::Relaxed);
+        }
+
+        enqueued
+    }
+
+    /// Releases up to `count` spans from the queue, and from the process-wide total.
+    fn release(&self, count: u64) {
+        let previous = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| {
+                Some(queued.saturating_sub(count))
+            })
+            .unwrap_or_else(|queued| queued);
+
+        let released = previous.min(count);
+        let _ = QUEUED.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |queued| {
+            Some(queued.saturating_sub(released))
+        });
+    }
+
+    /// Releases every span left in the queue.
+    fn clear(&self) {
+        self.release(u64::MAX);
+    }
+}
+
+/// A span exporter wrapper counting the exported and failed spans.
+#[derive(Debug)]
+pub struct CountingExporter<E> {
+    inner: E,
+    queue: QueueCounter,
+}
+
+impl<E: SpanExporter> CountingExporter<E> {
+    /// Creates a new `CountingExporter` wrapping the provided exporter.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The exporter sending the spans
+    /// * `queue` - The queue counter of the pipeline, shared with its `CountingProcessor`
+    ///
+    /// # Returns
+    ///
+    /// A new `CountingExporter` instance
+    pub fn new(inner: E, queue: QueueCounter) -> CountingExporter<E> {
+        CountingExporter { inner, queue }
+    }
+}
+
+impl<E: SpanExporter> SpanExporter for CountingExporter<E> {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let count = batch.len() as u64;
+        let result = self.inner.export(batch).await;
+
+        match &result {
+            Ok(()) => EXPORTED.fetch_add(count, Ordering::Relaxed),
+            Err(_) => EXPORT_ERRORS.fetch_add(count, Ordering::Relaxed),
+        };
+        self.queue.release(count);
+
+        result
+    }
+
+    fn shutdown_with_timeout(&mut self, timeout: Duration) -> OTelSdkResult {
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+/// A span processor wrapper counting the queued spans and dropping them when the queue is full.
+///
+/// The wrapped processor must export through a `CountingExporter` sharing the same
+/// `QueueCounter`, which releases the spans from the queue. Spans not sampled, e.g. those
+/// recorded by `RecordingSampler`, are never exported so they are not counted. Once shut
+/// down, spans are no longer counted, as the wrapped processor discards them, and the spans
+/// left in the queue are released.
+#[derive(Debug)]
+pub struct CountingProcessor<P> {
+    inner: P,
+    queue: QueueCounter,
+    max_queue_size: Option<u64>,
+    shutdown: AtomicBool,
+}
+
+impl<P: SpanProcessor> CountingProcessor<P> {
+    /// Creates a new `CountingProcessor` wrapping the provided processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The processor exporting the spans through a `CountingExporter`
+    /// * `queue` - The queue counter of the pipeline, shared with its `CountingExporter`
+    /// * `max_queue_size` - The number of spans queued at most, or `None` for no bound
+    ///
+    /// # Returns
+    ///
+    /// A new `CountingProcessor` instance
+    pub fn new(
+        inner: P,
+        queue: QueueCounter,
+        max_queue_size: Option<usize>,
+    ) -> CountingProcessor<P> {
+        CountingProcessor {
+            inner,
+            queue,
+            max_queue_size: max_queue_size.map(|size| size as u64),
+            shutdown: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for CountingProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        // Spans not sampled, e.g. record-only ones, are never handed to the exporter, and once
+        // shut down the wrapped processor discards the span without exporting it
+        if !span.span_context.is_sampled()
+            || self.shutdown.load(Ordering::Relaxed)
+            || self.queue.enqueue(self.max_queue_size)
+        {
+            self.inner.on_end(span);
+        } else {
+            DROPPED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.shutdown.store(true, Ordering::Relaxed);
+        let result = self.inner.shutdown_with_timeout(timeout);
+        // Spans not exported by now are discarded by the wrapped processor
+        self.queue.clear();
+
+        result
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporters::sampler::RecordingSampler;
+    use opentelemetry::trace::{
+        Span as _, SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, Tracer,
+        TracerProvider,
+    };
+    use opentelemetry_sdk::trace::{
+        InMemorySpanExporter, Sampler, SdkTracerProvider, SimpleSpanProcessor,
+    };
+
+    /// A processor keeping every span queued, as if the exporter never completed.
+    #[derive(Debug)]
+    struct StalledProcessor;
+
+    impl SpanProcessor for StalledProcessor {
+        fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+        fn on_end(&self, _span: SpanData) {}
+
+        fn force_flush(&self) -> OTelSdkResult {
+            Ok(())
+        }
+
+        fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    fn end_spans(provider: &SdkTracerProvider, count: usize) {
+        let tracer = provider.tracer("stats");
+        for _ in 0..count {
+            tracer.in_span("span", |_| {});
+        }
+    }
+
+    #[test]
+    fn drops_spans_once_the_queue_is_full() {
+        let queue = QueueCounter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(CountingProcessor::new(
+                StalledProcessor,
+                queue.clone(),
+                Some(2),
+            ))
+            .build();
+        let dropped = snapshot().dropped;
+
+        end_spans(&provider, 5);
+
+        assert_eq!(queue.get(), 2);
+        assert_eq!(snapshot().dropped - dropped, 3);
+    }
+
+    #[test]
+    fn pipelines_do_not_share_the_queue_bound() {
+        let full = QueueCounter::default();
+        let first = SdkTracerProvider::builder()
+            .with_span_processor(CountingProcessor::new(
+                StalledProcessor,
+                full.clone(),
+                Some(1),
+            ))
+            .build();
+        end_spans(&first, 1);
+
+        let queue = QueueCounter::default();
+        let second = SdkTracerProvider::builder()
+            .with_span_processor(CountingProcessor::new(
+                StalledProcessor,
+                queue.clone(),
+                Some(1),
+            ))
+            .build();
+        end_spans(&second, 1);
+
+        assert_eq!(full.get(), 1);
+        assert_eq!(queue.get(), 1);
+    }
+
+    #[test]
+    fn releases_exported_spans() {
+        let queue = QueueCounter::default();
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(CountingProcessor::new(
+                SimpleSpanProcessor::new(CountingExporter::new(exporter.clone(), queue.clone())),
+                queue.clone(),
+                Some(1),
+            ))
+            .build();
+
+        end_spans(&provider, 3);
+
+        assert_eq!(queue.get(), 0);
+        assert_eq!(exporter.get_finished_spans().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn releases_the_queue_on_shutdown() {
+        let queue = QueueCounter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(CountingProcessor::new(
+                StalledProcessor,
+                queue.clone(),
+                Some(2),
+            ))
+            .build();
+        end_spans(&provider, 2);
+
+        provider.shutdown().unwrap();
+
+        assert_eq!(queue.get(), 0);
+    }
+
+    #[test]
+    fn does_not_queue_spans_not_sampled() {
+        let queue = QueueCounter::default();
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_sampler(RecordingSampler::new(Sampler::ParentBased(Box::new(
+                Sampler::AlwaysOn,
+            ))))
+            .with_span_processor(CountingProcessor::new(
+                SimpleSpanProcessor::new(CountingExporter::new(exporter.clone(), queue.clone())),
+                queue.clone(),
+                Some(1),
+            ))
+            .build();
+        let tracer = provider.tracer("stats");
+        // Children of a parent not sampled are recorded only
+        let parent = Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from(1_u128),
+            SpanId::from(1_u64),
+            TraceFlags::default(),
+            true,
+            TraceState::default(),
+        ));
+
+        for _ in 0..3 {
+            let mut span = tracer.start_with_context("recorded", &parent);
+            assert!(span.is_recording());
+            span.end();
+        }
+        end_spans(&provider, 1);
+
+        let exported = exporter.get_finished_spans().unwrap();
+        assert_eq!(queue.get(), 0);
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].name, "span");
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Trace buffer processor.
+//!
+//! This module provides a span processor wrapper that buffers finished spans per trace and
+//! forwards them together once the trace completes, so the spans of a trace reach the
+//! exporter in the same batch instead of being split by the batch schedule.
+
+use opentelemetry::{
+    Context,
+    trace::{Span as _, TraceId},
+};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{Span, SpanData, SpanProcessor},
+};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Maximum number of traces buffered at once, bounding the memory used for buffering.
+const MAX_BUFFERED_TRACES: usize = 65_536;
+
+/// Minimum interval between two sweeps of the expired traces.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The spans of a trace waiting for the trace to complete.
+#[derive(Debug)]
+struct PendingTrace {
+    open: usize,
+    spans: Vec<SpanData>,
+    started: Instant,
+}
+
+#[derive(Debug)]
+struct Buffer {
+    traces: HashMap<TraceId, PendingTrace>,
+    last_sweep: Instant,
+}
+
+/// A span processor wrapper that forwards the spans of a trace once the trace completes.
+///
+/// Spans are tracked per trace from their start. A trace is complete when all of its spans
+/// started in this process have ended, which includes the end of its local root span; its
+/// spans are then forwarded to the wrapped processor at once. Traces still incomplete after
+/// `timeout` are forwarded as they are, so long-lived or leaked spans do not hold a trace
+/// forever. Flushing or shutting down the processor forwards all buffered spans.
+///
+/// If more than 65,536 traces are buffered at once, spans of new traces are forwarded
+/// without buffering.
+#[derive(Debug)]
+pub struct TraceBufferProcessor<P> {
+    inner: P,
+    timeout: Duration,
+    buffer: Mutex<Buffer>,
+}
+
+impl<P: SpanProcessor> TraceBufferProcessor<P> {
+    /// Creates a new `TraceBufferProcessor` wrapping the provided processor.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The processor receiving the spans of completed traces
+    /// * `timeout` - The maximum time a trace is buffered before being forwarded
+    ///
+    /// # Returns
+    ///
+    /// A new `TraceBufferProcessor` instance
+    pub fn new(inner: P, timeout: Duration) -> TraceBufferProcessor<P> {
+        TraceBufferProcessor {
+            inner,
+            timeout,
+            buffer: Mutex::new(Buffer {
+                traces: HashMap::new(),
+                last_sweep: Instant::now(),
+            }),
+        }
+    }
+
+    /// Forwards the spans of the provided traces to the wrapped processor.
+    fn forward(&self, traces: Vec<PendingTrace>) {
+        for span in traces.into_iter().flat_map(|trace| trace.spans) {
+            self.inner.on_end(span);
+        }
+    }
+
+    /// Removes and returns the traces buffered for longer than the timeout.
+    fn take_expired(&self, buffer: &mut Buffer) -> Vec<PendingTrace> {
+        if buffer.last_sweep.elapsed() < SWEEP_INTERVAL {
+            return Vec::new();
+        }
+        buffer.last_sweep = Instant::now();
+
+        let expired: Vec<TraceId> = buffer
+            .traces
+            .iter()
+            .filter(|(_, trace)| trace.started.elapsed() >= self.timeout)
+            .map(|(trace_id, _)| *trace_id)
+            .collect();
+
+        expired
+            .iter()
+            .filter_map(|trace_id| buffer.traces.remove(trace_id))
+            .collect()
+    }
+
+    /// Removes and returns all buffered traces.
+    fn take_all(&self) -> Vec<PendingTrace> {
+        match self.buffer.lock() {
+            Ok(mut buffer) => buffer.traces.drain().map(|(_, trace)| trace).collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for TraceBufferProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+
+        let Ok(mut buffer) = self.buffer.lock() else {
+            return;
+        };
+
+        let trace_id = span.span_context().trace_id();
+        if !buffer.traces.contains_key(&trace_id) && buffer.traces.len() >= MAX_BUFFERED_TRACES {
+            return;
+        }
+
+        buffer
+            .traces
+            .entry(trace_id)
+            .or_insert_with(|| PendingTrace {
+                open: 0,
+                spans: Vec::new(),
+                started: Instant::now(),
+            })
+            .open += 1;
+    }
+
+    fn on_end(&self, span: SpanData) {
+        let Ok(mut buffer) = self.buffer.lock() else {
+            self.inner.on_end(span);
+            return;
+        };
+
+        let trace_id = span.span_context.trace_id();
+        let mut ready = self.take_expired(&mut buffer);
+
+        match buffer.traces.get_mut(&trace_id) {
+            Some(trace) => {
+                trace.spans.push(span);
+                trace.open = trace.open.saturating_sub(1);
+
+                if trace.open == 0 {
+                    ready.extend(buffer.traces.remove(&trace_id));
+                }
+            }
+            None => ready.push(PendingTrace {
+                open: 0,
+                spans: vec![span],
+                started: Instant::now(),
+            }),
+        }
+
+        drop(buffer);
+        self.forward(ready);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.forward(self.take_all());
+        self.inner.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.forward(self.take_all());
+        self.inner.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.inner.set_resource(resource);
+    }
+}
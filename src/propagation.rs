@@ -0,0 +1,352 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Trace context propagation setup.
+//!
+//! This module builds the composite text map propagator registered globally at install
+//! time. The W3C trace context and baggage propagators are used by default, and other
+//! formats can be enabled for interoperability with non-W3C services.
+
+use crate::errors::TracesError;
+use opentelemetry::{
+    global,
+    propagation::{TextMapCompositePropagator, TextMapPropagator},
+};
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+use std::str::FromStr;
+use tracing::warn;
+
+/// A propagation format that can be enabled in the composite propagator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropagatorKind {
+    /// W3C Trace Context (`traceparent`/`tracestate`).
+    TraceContext,
+    /// W3C Baggage (`baggage`).
+    Baggage,
+    /// Zipkin B3 single header (`b3`), requires the `b3` feature.
+    B3,
+    /// Zipkin B3 multiple headers (`x-b3-*`), requires the `b3` feature.
+    B3Multi,
+    /// Jaeger (`uber-trace-id`), requires the `jaeger` feature.
+    Jaeger,
+    /// AWS X-Ray (`x-amzn-trace-id`), requires the `xray` feature.
+    ///
+    /// X-Ray only accepts trace IDs starting with the trace start time, so pair it with the
+    /// X-Ray ID generator, see `IdGeneratorKind::XRay`.
+    XRay,
+    /// Datadog (`x-datadog-trace-id`/`x-datadog-parent-id`), requires the `datadog` feature.
+    ///
+    /// Datadog trace IDs are 64 bits: only the lower 64 bits of the trace ID are injected,
+    /// and extracted trace IDs have their upper 64 bits set to zero, so a trace crossing a
+    /// Datadog-only service keeps its lower 64 bits but not its full W3C trace ID.
+    Datadog,
+}
+
+impl FromStr for PropagatorKind {
+    type Err = TracesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tracecontext" | "w3c" => Ok(PropagatorKind::TraceContext),
+            "baggage" => Ok(PropagatorKind::Baggage),
+            "b3" => Ok(PropagatorKind::B3),
+            "b3multi" => Ok(PropagatorKind::B3Multi),
+            "jaeger" => Ok(PropagatorKind::Jaeger),
+            "xray" => Ok(PropagatorKind::XRay),
+            "datadog" => Ok(PropagatorKind::Datadog),
+            _ => Err(TracesError::ConversionError),
+        }
+    }
+}
+
+/// The propagators enabled by default: W3C trace context and baggage.
+pub const DEFAULT_PROPAGATORS: [PropagatorKind; 2] =
+    [PropagatorKind::TraceContext, PropagatorKind::Baggage];
+
+/// Builds the propagator for a propagation format.
+///
+/// # Returns
+///
+/// The propagator, or `None` if the required feature is not enabled
+fn propagator(kind: PropagatorKind) -> Option<Box<dyn TextMapPropagator + Send + Sync>> {
+    match kind {
+        PropagatorKind::TraceContext => Some(Box::new(TraceContextPropagator::new())),
+        PropagatorKind::Baggage => Some(Box::new(BaggagePropagator::new())),
+        #[cfg(feature = "b3")]
+        PropagatorKind::B3 => Some(Box::new(opentelemetry_zipkin::Propagator::with_encoding(
+            opentelemetry_zipkin::B3Encoding::SingleHeader,
+        ))),
+        #[cfg(feature = "b3")]
+        PropagatorKind::B3Multi => Some(Box::new(opentelemetry_zipkin::Propagator::with_encoding(
+            opentelemetry_zipkin::B3Encoding::MultipleHeader,
+        ))),
+        #[cfg(feature = "jaeger")]
+        PropagatorKind::Jaeger => {
+            Some(Box::new(opentelemetry_jaeger_propagator::Propagator::new()))
+        }
+        #[cfg(feature = "xray")]
+        PropagatorKind::XRay => Some(Box::new(opentelemetry_aws::trace::XrayPropagator::new())),
+        #[cfg(feature = "datadog")]
+        PropagatorKind::Datadog => Some(Box::new(opentelemetry_datadog::DatadogPropagator::new())),
+        #[allow(unreachable_patterns)]
+        kind => {
+            warn!(propagator = ?kind, "propagator requires a disabled feature, skipping");
+            None
+        }
+    }
+}
+
+/// Builds a composite propagator from the enabled propagation formats.
+///
+/// On extraction, later propagators see the context produced by the earlier ones.
+///
+/// # Arguments
+///
+/// * `kinds` - The propagation formats to enable
+///
+/// # Returns
+///
+/// The composite propagator
+pub fn composite(kinds: &[PropagatorKind]) -> TextMapCompositePropagator {
+    composite_with(kinds, Vec::new())
+}
+
+/// Builds a composite propagator from the enabled propagation formats and custom propagators.
+///
+/// The custom propagators are appended after the enabled formats, in the given order.
+///
+/// # Arguments
+///
+/// * `kinds` - The propagation formats to enable
+/// * `extra` - The custom propagators appended to the enabled formats
+///
+/// # Returns
+///
+/// The composite propagator
+pub fn composite_with(
+    kinds: &[PropagatorKind],
+    extra: Vec<Box<dyn TextMapPropagator + Send + Sync>>,
+) -> TextMapCompositePropagator {
+    TextMapCompositePropagator::new(
+        kinds
+            .iter()
+            .filter_map(|kind| propagator(*kind))
+            .chain(extra)
+            .collect(),
+    )
+}
+
+/// Registers the composite propagator of the enabled formats as the global propagator.
+///
+/// # Arguments
+///
+/// * `kinds` - The propagation formats to enable
+pub fn install(kinds: &[PropagatorKind]) {
+    global::set_text_map_propagator(composite(kinds));
+}
+
+/// Registers the composite propagator of the enabled formats and custom propagators as the
+/// global propagator.
+///
+/// # Arguments
+///
+/// * `kinds` - The propagation formats to enable
+/// * `extra` - The custom propagators appended to the enabled formats
+pub fn install_with(
+    kinds: &[PropagatorKind],
+    extra: Vec<Box<dyn TextMapPropagator + Send + Sync>>,
+) {
+    global::set_text_map_propagator(composite_with(kinds, extra));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::TracesConfigs, helpers, injectors, test_utils};
+    use opentelemetry::{
+        Context,
+        propagation::{Extractor, Injector, text_map_propagator::FieldIter},
+        trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState},
+    };
+    use std::collections::HashMap;
+
+    /// Returns a context continuing a sampled remote span.
+    fn remote_context() -> Context {
+        Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from(0x0af7_6519_16cd_43dd_8448_eb21_1c80_319c_u128),
+            SpanId::from(0x00f0_67aa_0ba9_02b7_u64),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        ))
+    }
+
+    /// Injects the context with the propagation format and extracts it back.
+    ///
+    /// # Returns
+    ///
+    /// The injected headers and the extracted span context
+    fn round_trip(kind: PropagatorKind, cx: &Context) -> (HashMap<String, String>, SpanContext) {
+        let propagator = composite(&[kind]);
+        let mut headers = HashMap::new();
+        propagator.inject_context(cx, &mut headers);
+
+        let extracted = propagator.extract(&headers);
+        let span_context = extracted.span().span_context().clone();
+
+        (headers, span_context)
+    }
+
+    #[test]
+    fn round_trips_the_w3c_trace_context() {
+        let cx = remote_context();
+
+        let (headers, extracted) = round_trip(PropagatorKind::TraceContext, &cx);
+
+        assert!(headers.contains_key("traceparent"));
+        assert_eq!(&extracted, cx.span().span_context());
+    }
+
+    #[test]
+    fn injects_no_baggage_header_when_baggage_is_disabled() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_BAGGAGE_ENABLED", "false")]);
+        install(&TracesConfigs::new().propagators);
+        let cx = helpers::set_baggage(&remote_context(), "tenant.id", "acme");
+
+        let mut headers = HashMap::new();
+        injectors::inject(&cx, &mut headers);
+
+        assert!(headers.contains_key("traceparent"));
+        assert!(!headers.contains_key("baggage"));
+    }
+
+    /// A custom propagator carrying the tenant of the baggage in an `x-tenant` header.
+    #[derive(Debug)]
+    struct TenantPropagator {
+        fields: Vec<String>,
+    }
+
+    impl TenantPropagator {
+        fn new() -> TenantPropagator {
+            TenantPropagator {
+                fields: vec!["x-tenant".to_owned()],
+            }
+        }
+    }
+
+    impl TextMapPropagator for TenantPropagator {
+        fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+            if let Some(tenant) = helpers::get_baggage(cx, "tenant.id") {
+                injector.set("x-tenant", tenant);
+            }
+        }
+
+        fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+            match extractor.get("x-tenant") {
+                Some(tenant) => helpers::set_baggage(cx, "tenant.id", tenant),
+                None => cx.clone(),
+            }
+        }
+
+        fn fields(&self) -> FieldIter<'_> {
+            FieldIter::new(&self.fields)
+        }
+    }
+
+    #[test]
+    fn appends_the_custom_propagators() {
+        let propagator = composite_with(
+            &[PropagatorKind::TraceContext],
+            vec![Box::new(TenantPropagator::new())],
+        );
+        let cx = helpers::set_baggage(&remote_context(), "tenant.id", "acme");
+
+        let mut headers = HashMap::new();
+        propagator.inject_context(&cx, &mut headers);
+        let extracted = propagator.extract(&headers);
+
+        assert_eq!(headers.get("x-tenant").map(String::as_str), Some("acme"));
+        assert!(!headers.contains_key("baggage"));
+        assert_eq!(
+            helpers::get_baggage(&extracted, "tenant.id"),
+            Some("acme".to_owned())
+        );
+        assert_eq!(extracted.span().span_context(), cx.span().span_context());
+    }
+
+    #[test]
+    fn installs_the_custom_propagators_globally() {
+        let _lock = test_utils::lock();
+        install_with(
+            &DEFAULT_PROPAGATORS,
+            vec![Box::new(TenantPropagator::new())],
+        );
+        let cx = helpers::set_baggage(&remote_context(), "tenant.id", "acme");
+
+        let mut headers = HashMap::new();
+        injectors::inject(&cx, &mut headers);
+
+        assert!(headers.contains_key("traceparent"));
+        assert_eq!(headers.get("x-tenant").map(String::as_str), Some("acme"));
+    }
+
+    #[cfg(feature = "b3")]
+    #[test]
+    fn round_trips_the_b3_single_header() {
+        let cx = remote_context();
+
+        let (headers, extracted) = round_trip(PropagatorKind::B3, &cx);
+
+        assert!(headers.contains_key("b3"));
+        assert!(!headers.contains_key("x-b3-traceid"));
+        assert_eq!(&extracted, cx.span().span_context());
+    }
+
+    #[cfg(feature = "b3")]
+    #[test]
+    fn round_trips_the_b3_multiple_headers() {
+        let cx = remote_context();
+
+        let (headers, extracted) = round_trip(PropagatorKind::B3Multi, &cx);
+
+        assert_eq!(
+            headers.get("x-b3-traceid").map(String::as_str),
+            Some("0af7651916cd43dd8448eb211c80319c")
+        );
+        assert!(!headers.contains_key("b3"));
+        assert_eq!(&extracted, cx.span().span_context());
+    }
+
+    #[cfg(feature = "jaeger")]
+    #[test]
+    fn round_trips_the_jaeger_header() {
+        let cx = remote_context();
+
+        let (headers, extracted) = round_trip(PropagatorKind::Jaeger, &cx);
+
+        assert!(headers.contains_key("uber-trace-id"));
+        assert_eq!(&extracted, cx.span().span_context());
+    }
+
+    #[cfg(feature = "datadog")]
+    #[test]
+    fn round_trips_the_lower_64_bits_with_datadog() {
+        let cx = remote_context();
+        let sent = cx.span().span_context();
+
+        let (headers, extracted) = round_trip(PropagatorKind::Datadog, &cx);
+
+        assert_eq!(
+            headers.get("x-datadog-trace-id").map(String::as_str),
+            Some(0x8448_eb21_1c80_319c_u64.to_string().as_str())
+        );
+        assert_eq!(
+            extracted.trace_id(),
+            TraceId::from(0x8448_eb21_1c80_319c_u128)
+        );
+        assert_eq!(extracted.span_id(), sent.span_id());
+        assert!(extracted.is_sampled());
+    }
+}
@@ -7,19 +7,68 @@
 //! This module provides functionality to initialize and configure the OpenTelemetry trace provider
 //! based on the application configuration.
 
+use crate::config::{FailurePolicy, TracesConfigs};
 use crate::errors::TracesError;
-use crate::exporters;
-use opentelemetry_sdk::trace::SdkTracerProvider;
-use tracing::info;
+use crate::exporters::{self, ExporterKind, id_generator, sampler};
+use crate::{processors, propagation};
+use configs::app::AppConfigs;
+#[cfg(feature = "otlp")]
+use configs::otlp::OTLPConfigs;
+#[cfg(feature = "tracing-layer")]
+use opentelemetry::trace::TracerProvider;
+use opentelemetry::{
+    InstrumentationScope, KeyValue,
+    global::{self, BoxedTracer},
+    propagation::TextMapPropagator,
+};
+#[cfg(feature = "tracing-layer")]
+use opentelemetry_sdk::trace::SdkTracer;
+use opentelemetry_sdk::{
+    error::OTelSdkError,
+    trace::{IdGenerator, SdkTracerProvider},
+};
+use std::{
+    sync::{Mutex, OnceLock, mpsc},
+    thread,
+    time::Duration,
+};
+use tracing::{error, info, warn};
+
+/// The tracer provider registered by the last successful install.
+static INSTALLED_PROVIDER: Mutex<Option<SdkTracerProvider>> = Mutex::new(None);
+
+/// The service identity applied to the resource by the last successful install.
+static SERVICE_IDENTITY: Mutex<Option<ServiceIdentity>> = Mutex::new(None);
+
+/// The name of the service tracer, read once from `AppConfigs`.
+static TRACER_NAME: OnceLock<String> = OnceLock::new();
 
 /// Initialize the OpenTelemetry trace provider based on feature flags.
 ///
 /// This function selects and configures the appropriate tracer exporter based on enabled features:
 /// - When both `otlp` and `stdout` features are enabled, OTLP takes precedence
 /// - When only `otlp` is enabled, uses the OTLP gRPC exporter
+/// - When `otlp-http` is enabled without `otlp`, uses the OTLP HTTP exporter
 /// - When only `stdout` is enabled, uses the stdout exporter for console output
 /// - When no features are enabled, uses a no-op tracer
 ///
+/// If the exporter cannot be installed and `TracesConfigs::exporter_failure_policy` is
+/// `FailurePolicy::Noop`, the error is logged and the no-op tracer is installed instead,
+/// so a misconfigured collector degrades tracing rather than the whole service.
+///
+/// Likewise, if the `otlp` feature is enabled without an OTLP endpoint and
+/// `TracesConfigs::missing_endpoint_policy` is `FailurePolicy::Noop`, a warning is logged
+/// and the no-op tracer is installed, so services can run where no collector is configured.
+///
+/// Installing again replaces the global tracer provider, the no-op one included: the previously
+/// installed provider is flushed and shut down once the new one is registered, with a warning,
+/// so its pending spans are exported rather than lost. Clones of the previous provider are
+/// shut down too.
+///
+/// Features only make exporters available: if `TracesConfigs::enabled` is `false`, the
+/// no-op tracer is installed regardless of the enabled features, e.g. to disable export
+/// in CI with the same binary.
+///
 /// # Returns
 ///
 /// * `Ok(SdkTracerProvider)` if initialization is successful
@@ -35,27 +84,928 @@ use tracing::info;
 /// }
 /// ```
 pub fn install() -> Result<SdkTracerProvider, TracesError> {
+    install_with_id_generator(id_generator::configured(TracesConfigs::new().id_generator))
+}
+
+/// Initialize the OpenTelemetry trace provider and return the service tracer with it.
+///
+/// This behaves like `install`, and also returns the tracer named after the application,
+/// as returned by `tracer`, so spans can be started right away.
+///
+/// # Returns
+///
+/// * `Ok((SdkTracerProvider, BoxedTracer))` if initialization is successful
+/// * `Err(TracesError)` if initialization fails or required features are not enabled
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{helpers, provider};
+///
+/// fn main() {
+///     let (tracer_provider, tracer) =
+///         provider::install_with_tracer().expect("Failed to initialize tracing");
+///     let ctx = helpers::ctx(&tracer, SpanKind::Internal, "startup");
+/// }
+/// ```
+pub fn install_with_tracer() -> Result<(SdkTracerProvider, BoxedTracer), TracesError> {
+    let provider = install()?;
+
+    Ok((provider, tracer()))
+}
+
+/// Initialize the OpenTelemetry trace provider with additional custom propagators.
+///
+/// This behaves like `install`, then registers as the global propagator the formats of
+/// `TracesConfigs::propagators` followed by the custom propagators, in the given order, e.g.
+/// to keep propagating an in-house header that legacy systems depend on.
+///
+/// On injection every propagator writes its own fields. On extraction the propagators run
+/// in order, each one on the context returned by the previous ones, so a span context
+/// extracted by a later propagator replaces the one extracted by an earlier propagator:
+/// put the preferred custom propagator last, and keep in mind that the custom propagators
+/// take precedence over the configured formats when both headers are present.
+///
+/// # Arguments
+///
+/// * `extra` - The custom propagators appended to the configured formats
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if initialization is successful
+/// * `Err(TracesError)` if initialization fails or required features are not enabled
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::{
+///     Context,
+///     propagation::{Extractor, Injector, TextMapPropagator, text_map_propagator::FieldIter},
+/// };
+/// use traces::provider;
+///
+/// #[derive(Debug)]
+/// struct LegacyPropagator {
+///     fields: Vec<String>,
+/// }
+///
+/// impl TextMapPropagator for LegacyPropagator {
+///     fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+///         // Write the legacy header from the span context of `cx`
+///     }
+///
+///     fn extract_with_context(&self, cx: &Context, extractor: &dyn Extractor) -> Context {
+///         // Read the legacy header and return `cx` with the remote span context
+///         cx.clone()
+///     }
+///
+///     fn fields(&self) -> FieldIter<'_> {
+///         FieldIter::new(&self.fields)
+///     }
+/// }
+///
+/// fn main() {
+///     let legacy = LegacyPropagator { fields: vec!["x-legacy-trace".to_owned()] };
+///     let tracer_provider = provider::install_with_propagators(vec![Box::new(legacy)])
+///         .expect("Failed to initialize tracing");
+/// }
+/// ```
+pub fn install_with_propagators(
+    extra: Vec<Box<dyn TextMapPropagator + Send + Sync>>,
+) -> Result<SdkTracerProvider, TracesError> {
+    let provider = install()?;
+
+    propagation::install_with(&TracesConfigs::new().propagators, extra);
+
+    Ok(provider)
+}
+
+/// Initialize the OpenTelemetry trace provider using a custom trace and span ID generator.
+///
+/// This behaves like `install`, but new trace and span IDs are produced by the provided
+/// generator, e.g. to embed shard information in the identifiers. See
+/// `exporters::id_generator` for guidance on implementing a spec-compliant generator.
+/// When falling back to the no-op tracer, the default random generator is used.
+///
+/// # Arguments
+///
+/// * `id_generator` - The generator used for new trace and span IDs
+///
+/// # Returns
+///
+/// * `Ok(SdkTracerProvider)` if initialization is successful
+/// * `Err(TracesError)` if initialization fails or required features are not enabled
+pub fn install_with_id_generator<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
     info!("traces::install configuring tracer provider");
 
+    let cfgs = TracesConfigs::new();
+
+    let installed = if !cfgs.enabled {
+        info!("traces::install tracing disabled, installing noop tracer");
+        exporters::noop::install_with_id_generator(id_generator)
+    } else {
+        install_exporter(id_generator, &cfgs)
+    };
+
+    let app_cfgs = AppConfigs::new();
+    let identity = ServiceIdentity {
+        name: cfgs.service_name(&app_cfgs),
+        ..ServiceIdentity::from(&app_cfgs)
+    };
+
+    let provider = match installed {
+        Ok(provider) => Ok(provider),
+        Err(err) if cfgs.exporter_failure_policy == FailurePolicy::Noop => {
+            error!(
+                error = err.to_string(),
+                "failure to install exporter, falling back to noop tracer"
+            );
+            exporters::noop::install()
+        }
+        Err(err) => Err(err),
+    }?;
+
+    if let Ok(mut installed) = SERVICE_IDENTITY.lock() {
+        installed.replace(identity);
+    }
+
+    let previous = match INSTALLED_PROVIDER.lock() {
+        Ok(mut installed) => installed.replace(provider.clone()),
+        Err(_) => None,
+    };
+
+    // The new provider is already global, so the previous one no longer receives spans
+    if let Some(previous) = previous {
+        warn!("traces::install tracer provider already installed, shutting down the previous one");
+
+        match shutdown(&previous) {
+            Ok(()) | Err(TracesError::AlreadyShutdownError) => {}
+            Err(err) => error!(
+                error = err.to_string(),
+                "failure to shutdown the previous tracer provider"
+            ),
+        }
+    }
+
+    Ok(provider)
+}
+
+/// Whether the OTLP gRPC exporter is skipped because no endpoint is configured.
+#[cfg(feature = "otlp")]
+fn skip_missing_endpoint(cfgs: &TracesConfigs) -> bool {
+    skips_endpoint(cfgs.missing_endpoint_policy, &OTLPConfigs::new().endpoint)
+}
+
+/// Whether the endpoint is missing and the policy installs the no-op tracer rather than
+/// letting the OTLP gRPC exporter fail on it.
+#[cfg(feature = "otlp")]
+fn skips_endpoint(policy: FailurePolicy, endpoint: &str) -> bool {
+    policy == FailurePolicy::Noop && endpoint.trim().is_empty()
+}
+
+/// Whether the OTLP gRPC exporter is skipped because no endpoint is configured.
+#[cfg(not(feature = "otlp"))]
+fn skip_missing_endpoint(_cfgs: &TracesConfigs) -> bool {
+    false
+}
+
+/// Installs the exporter selected by `TracesConfigs::exporter`.
+fn install_exporter<G: IdGenerator + 'static>(
+    id_generator: G,
+    cfgs: &TracesConfigs,
+) -> Result<SdkTracerProvider, TracesError> {
+    match selected_exporter(cfgs.exporter) {
+        ExporterKind::Noop => {
+            info!("traces::install noop exporter selected, installing noop tracer");
+            exporters::noop::install_with_id_generator(id_generator)
+        }
+        #[cfg(feature = "stdout")]
+        ExporterKind::Stdout => exporters::stdout::install_with_id_generator(id_generator),
+        _ if skip_missing_endpoint(cfgs) => {
+            warn!("traces::install OTLP endpoint is not configured, installing noop tracer");
+            exporters::noop::install_with_id_generator(id_generator)
+        }
+        _ => install_default_exporter(id_generator),
+    }
+}
+
+/// Returns the exporter to install, falling back to `auto` when its feature is not compiled in.
+fn selected_exporter(kind: ExporterKind) -> ExporterKind {
+    match kind {
+        ExporterKind::Otlp if !cfg!(any(feature = "otlp", feature = "otlp-http")) => {
+            warn!(
+                "traces::install otlp exporter selected without the otlp or otlp-http feature, falling back to auto"
+            );
+            ExporterKind::Auto
+        }
+        ExporterKind::Stdout if !cfg!(feature = "stdout") => {
+            warn!(
+                "traces::install stdout exporter selected without the stdout feature, falling back to auto"
+            );
+            ExporterKind::Auto
+        }
+        kind => kind,
+    }
+}
+
+/// Installs the exporter selected by the enabled feature flags.
+fn install_default_exporter<G: IdGenerator + 'static>(
+    id_generator: G,
+) -> Result<SdkTracerProvider, TracesError> {
     #[cfg(all(feature = "otlp", feature = "stdout"))]
     {
         // When both features are enabled, prefer OTLP
-        let tracer = exporters::otlp_grpc::install()?;
+        let tracer = exporters::otlp_grpc::install_with_id_generator(id_generator)?;
         return Ok(tracer);
     }
 
     #[cfg(all(feature = "otlp", not(feature = "stdout")))]
     {
-        let tracer = exporters::otlp_grpc::install()?;
+        let tracer = exporters::otlp_grpc::install_with_id_generator(id_generator)?;
+        return Ok(tracer);
+    }
+
+    #[cfg(all(feature = "otlp-http", not(feature = "otlp")))]
+    {
+        // gRPC takes precedence over HTTP when both are enabled
+        let tracer = exporters::otlp_http::install_with_id_generator(id_generator)?;
         return Ok(tracer);
     }
 
-    #[cfg(all(feature = "stdout", not(feature = "otlp")))]
+    #[cfg(all(feature = "stdout", not(any(feature = "otlp", feature = "otlp-http"))))]
     {
-        let tracer = exporters::stdout::install()?;
+        let tracer = exporters::stdout::install_with_id_generator(id_generator)?;
         return Ok(tracer);
     }
 
-    #[cfg(not(any(feature = "stdout", feature = "otlp")))]
-    return exporters::noop::install();
+    #[cfg(not(any(feature = "stdout", feature = "otlp", feature = "otlp-http")))]
+    return exporters::noop::install_with_id_generator(id_generator);
+}
+
+/// Flushes all pending spans of the tracer provider without shutting it down.
+///
+/// This is useful to periodically drain the batch exporter in long-running processes.
+///
+/// # Arguments
+///
+/// * `provider` - The tracer provider returned by `install`
+///
+/// # Returns
+///
+/// * `Ok(())` if all pending spans were exported
+/// * `Err(TracesError::AlreadyShutdownError)` if the provider was already shut down
+/// * `Err(TracesError::ProviderFlushError)` if the flush fails
+pub fn force_flush(provider: &SdkTracerProvider) -> Result<(), TracesError> {
+    provider.force_flush().map_err(|err| match err {
+        OTelSdkError::AlreadyShutdown => TracesError::AlreadyShutdownError,
+        err => {
+            error!(error = err.to_string(), "failure to flush tracer provider");
+            TracesError::ProviderFlushError
+        }
+    })
+}
+
+/// Flushes all pending spans of the tracer provider, waiting at most `timeout`.
+///
+/// This is meant for serverless and FaaS deployments, where the runtime freezes the process
+/// between invocations: call it at the end of each handler, before returning the response,
+/// so the spans of the invocation are exported rather than lost or delayed until the next
+/// one. The flush runs on a separate thread; on timeout it keeps running in the background
+/// and this function returns, so the handler is never blocked longer than `timeout`.
+///
+/// # Arguments
+///
+/// * `provider` - The tracer provider returned by `install`
+/// * `timeout` - The maximum time to wait for the flush
+///
+/// # Returns
+///
+/// * `Ok(())` if all pending spans were exported in time
+/// * `Err(TracesError::FlushTimeoutError)` if the flush did not complete in time
+/// * `Err(TracesError::AlreadyShutdownError)` if the provider was already shut down
+/// * `Err(TracesError::ProviderFlushError)` if the flush fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry_sdk::trace::SdkTracerProvider;
+/// use std::time::Duration;
+/// use traces::provider;
+///
+/// fn handler(tracer_provider: &SdkTracerProvider) -> String {
+///     let response = String::from("done");
+///
+///     if let Err(err) = provider::flush_with_timeout(tracer_provider, Duration::from_millis(500)) {
+///         tracing::warn!(error = err.to_string(), "spans of the invocation may be lost");
+///     }
+///
+///     response
+/// }
+/// ```
+pub fn flush_with_timeout(
+    provider: &SdkTracerProvider,
+    timeout: Duration,
+) -> Result<(), TracesError> {
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let provider = provider.clone();
+
+    thread::spawn(move || {
+        let _ = sender.send(force_flush(&provider));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(_) => {
+            warn!(
+                timeout_ms = timeout.as_millis() as u64,
+                "traces::flush tracer provider flush timed out"
+            );
+            Err(TracesError::FlushTimeoutError)
+        }
+    }
+}
+
+/// Flushes all pending spans and shuts down the tracer provider.
+///
+/// When using the OTLP batch exporter this must be called before `main` returns,
+/// otherwise the last batch of spans may be lost. Calling it more than once is safe:
+/// subsequent calls return `TracesError::AlreadyShutdownError`.
+///
+/// # Arguments
+///
+/// * `provider` - The tracer provider returned by `install`
+///
+/// # Returns
+///
+/// * `Ok(())` if the provider was flushed and shut down
+/// * `Err(TracesError::AlreadyShutdownError)` if the provider was already shut down
+/// * `Err(TracesError)` if the flush or the shutdown fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::provider;
+///
+/// fn main() {
+///     let tracer_provider = provider::install().expect("Failed to initialize tracing");
+///
+///     // Application code...
+///
+///     provider::shutdown(&tracer_provider).expect("Failed to shutdown tracing");
+/// }
+/// ```
+pub fn shutdown(provider: &SdkTracerProvider) -> Result<(), TracesError> {
+    force_flush(provider)?;
+
+    provider.shutdown().map_err(|err| match err {
+        OTelSdkError::AlreadyShutdown => TracesError::AlreadyShutdownError,
+        err => {
+            error!(
+                error = err.to_string(),
+                "failure to shutdown tracer provider"
+            );
+            TracesError::ProviderShutdownError
+        }
+    })?;
+
+    info!("traces::shutdown tracer provider shutdown");
+
+    Ok(())
+}
+
+/// Changes the sampling ratio of the installed tracer provider at runtime.
+///
+/// The new ratio applies to the next root spans, without reinstalling the provider, e.g. to
+/// sample more traces during an incident. Spans with a parent still follow the parent's
+/// decision. The ratio is shared through an atomic, so this may be called from any thread,
+/// such as an admin endpoint handler. Each provider has its own ratio: only the installed
+/// provider is updated, providers built without being installed, e.g. with
+/// `otlp_grpc::Builder::build`, keep their configured ratio, and installing again starts
+/// from the configured ratio.
+///
+/// Only the default ratio-based sampler is reloadable: the ratio has no effect in local
+/// environments, in `TracesConfigs::always_off_environments`, or when the sampler is
+/// selected by `OTEL_TRACES_SAMPLER` or `TracesConfigs::session_baggage_key`.
+///
+/// # Arguments
+///
+/// * `ratio` - The fraction of traces to sample, between 0.0 and 1.0
+///
+/// # Returns
+///
+/// * `Ok(())` if the ratio was updated, or ignored as no tracer provider is installed
+/// * `Err(TracesError::InvalidConfigError)` if the ratio is not between 0.0 and 1.0
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::provider;
+///
+/// fn on_incident() {
+///     provider::set_sampling_ratio(1.0).expect("Invalid sampling ratio");
+/// }
+/// ```
+pub fn set_sampling_ratio(ratio: f64) -> Result<(), TracesError> {
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err(TracesError::InvalidConfigError(format!(
+            "sampling ratio {ratio} is out of range, expected 0-1"
+        )));
+    }
+
+    match sampler::installed_ratio_sampler() {
+        Some(installed) => {
+            installed.set_ratio(ratio);
+            info!(ratio = ratio, "traces::sampler sampling ratio updated");
+        }
+        None => warn!(
+            ratio = ratio,
+            "traces::sampler no tracer provider installed, sampling ratio ignored"
+        ),
+    }
+
+    Ok(())
+}
+
+/// A snapshot of the span pipeline counters, see `stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Spans exported successfully.
+    pub exported: u64,
+    /// Spans dropped because the export queue was full.
+    pub dropped: u64,
+    /// Spans of the failed export requests.
+    pub export_errors: u64,
+    /// Spans queued for export and not yet exported or failed.
+    pub queued: u64,
+}
+
+/// Returns a snapshot of the span pipeline counters.
+///
+/// The counters cover the pipelines of the OTLP gRPC and OTLP HTTP exporters, in both export
+/// modes, and of the file exporter, since the process started and across installs. Spans
+/// printed by the stdout exporter, captured by the in-memory exporter or handed to custom span
+/// processors are not counted. Spans are only dropped by the batch export mode, so a growing
+/// `dropped` count under load means `TRACES_BATCH_MAX_QUEUE_SIZE` is too small for the export
+/// throughput. Spans dropped by the sampler, the span filters or the
+/// per-trace span limit are not counted. The counters are read independently, so a snapshot
+/// taken while spans are exported may be slightly inconsistent.
+///
+/// # Returns
+///
+/// The current counters
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::provider;
+///
+/// fn report() {
+///     let stats = provider::stats();
+///     tracing::info!(
+///         exported = stats.exported,
+///         dropped = stats.dropped,
+///         export_errors = stats.export_errors,
+///         "span pipeline stats"
+///     );
+/// }
+/// ```
+pub fn stats() -> Stats {
+    processors::stats::snapshot()
+}
+
+/// Returns a `tracing` layer bridging `tracing` spans to OpenTelemetry spans.
+///
+/// The layer uses a tracer of the provider registered by `install`, named after the
+/// application, so spans created with the `tracing` macros are exported through the same
+/// pipeline. If tracing was not installed yet, `None` is returned, which `tracing-subscriber`
+/// treats as a no-op layer.
+///
+/// # Returns
+///
+/// The OpenTelemetry layer, or `None` if no provider was installed
+///
+/// # Examples
+///
+/// ```no_run
+/// use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+/// use traces::provider;
+///
+/// #[tracing::instrument]
+/// fn handle_order(order_id: u64) {
+///     tracing::info!("processing order");
+/// }
+///
+/// fn main() {
+///     let tracer_provider = provider::install().expect("Failed to initialize tracing");
+///     tracing_subscriber::registry().with(provider::layer()).init();
+///
+///     // Exported as an OpenTelemetry span named `handle_order`
+///     handle_order(42);
+///
+///     provider::shutdown(&tracer_provider).expect("Failed to shutdown tracing");
+/// }
+/// ```
+#[cfg(feature = "tracing-layer")]
+pub fn layer<S>() -> Option<tracing_opentelemetry::OpenTelemetryLayer<S, SdkTracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    let provider = INSTALLED_PROVIDER.lock().ok()?.clone()?;
+    let tracer = provider.tracer_with_scope(scope(tracer_name()));
+
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Returns the tracer of the service, named after the application.
+///
+/// The name is the service name, `AppConfigs::name` unless overridden by
+/// `TracesConfigs::service_name`, the same value set as `service.name` on the resource at
+/// install time, so call sites do not need to repeat the service name. The instrumentation
+/// scope version is the version of this crate.
+///
+/// # Returns
+///
+/// The service tracer from the global tracer provider
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{helpers, provider};
+///
+/// fn process_request() {
+///     let ctx = helpers::ctx(&provider::tracer(), SpanKind::Server, "process_request");
+/// }
+/// ```
+pub fn tracer() -> BoxedTracer {
+    global::tracer_with_scope(scope(tracer_name()))
+}
+
+/// Returns a tracer with an explicit instrumentation scope name and version.
+///
+/// Spans started by the tracer are exported with `otel.scope.name` and `otel.scope.version`
+/// set accordingly, so backends can filter spans by the library or module that created them.
+///
+/// # Arguments
+///
+/// * `name` - The instrumentation scope name, e.g. the crate or module name
+/// * `version` - The instrumentation scope version
+///
+/// # Returns
+///
+/// The tracer from the global tracer provider
+///
+/// # Examples
+///
+/// ```no_run
+/// use opentelemetry::trace::SpanKind;
+/// use traces::{helpers, provider};
+///
+/// fn charge() {
+///     let tracer = provider::tracer_with_scope("payments", env!("CARGO_PKG_VERSION"));
+///     let ctx = helpers::ctx(&tracer, SpanKind::Internal, "charge");
+/// }
+/// ```
+pub fn tracer_with_scope(name: &str, version: &str) -> BoxedTracer {
+    global::tracer_with_scope(
+        InstrumentationScope::builder(name.to_owned())
+            .with_version(version.to_owned())
+            .build(),
+    )
+}
+
+/// Returns the instrumentation scope of the tracers created by this crate.
+///
+/// The scope version is the version of this crate.
+pub(crate) fn scope(name: &'static str) -> InstrumentationScope {
+    InstrumentationScope::builder(name)
+        .with_version(env!("CARGO_PKG_VERSION"))
+        .build()
+}
+
+/// Returns the cached name of the service tracer.
+fn tracer_name() -> &'static str {
+    TRACER_NAME.get_or_init(|| TracesConfigs::new().service_name(&AppConfigs::new()))
+}
+
+/// The identity of the service, as set on the resource of the tracer provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceIdentity {
+    /// The service name, the `service.name` resource attribute.
+    pub name: String,
+    /// The service namespace, the `service.namespace` resource attribute.
+    pub namespace: String,
+    /// The deployment environment, the `environment` resource attribute.
+    pub env: String,
+}
+
+impl ServiceIdentity {
+    /// Returns the identity as attributes, keyed like the resource attributes.
+    pub fn attributes(&self) -> Vec<KeyValue> {
+        vec![
+            KeyValue::new("service.name", self.name.clone()),
+            KeyValue::new("service.namespace", self.namespace.clone()),
+            KeyValue::new("environment", self.env.clone()),
+        ]
+    }
+}
+
+impl From<&AppConfigs> for ServiceIdentity {
+    fn from(app_cfgs: &AppConfigs) -> Self {
+        ServiceIdentity {
+            name: app_cfgs.name.clone(),
+            namespace: format!("{}", app_cfgs.namespace),
+            env: format!("{}", app_cfgs.env),
+        }
+    }
+}
+
+/// Returns the service identity applied to the resource by the last install.
+///
+/// The identity is cached at install time from the same `AppConfigs` as the resource, so
+/// logging code can reuse it without reading the configuration again.
+///
+/// # Returns
+///
+/// The service identity, or `None` if tracing was not installed yet
+///
+/// # Examples
+///
+/// ```no_run
+/// use traces::provider;
+///
+/// fn main() {
+///     let tracer_provider = provider::install().expect("Failed to initialize tracing");
+///
+///     if let Some(identity) = provider::service_identity() {
+///         tracing::info!(service = identity.name, env = identity.env, "service started");
+///     }
+/// }
+/// ```
+pub fn service_identity() -> Option<ServiceIdentity> {
+    SERVICE_IDENTITY.lock().ok()?.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        helpers::{self, SamplingState},
+        test_utils,
+    };
+    use opentelemetry::trace::{SpanId, SpanKind, TraceContextExt, TraceId, Tracer};
+    use opentelemetry_sdk::{
+        error::OTelSdkResult,
+        trace::{SpanData, SpanExporter},
+    };
+    use std::time::Instant;
+
+    /// Installs twice and asserts the second provider replaced the first one globally.
+    fn assert_reinstall_replaces_global_provider() {
+        let first = install().expect("first install");
+        let second = install().expect("second install");
+
+        assert_eq!(shutdown(&first), Err(TracesError::AlreadyShutdownError));
+
+        // Spans of a shut down provider have an invalid context, those of the noop one are dropped
+        let ctx = helpers::ctx(&tracer(), SpanKind::Internal, "after reinstall");
+        assert_eq!(helpers::sampling_decision(&ctx), SamplingState::Drop);
+
+        shutdown(&second).expect("shutdown");
+    }
+
+    #[test]
+    fn reinstall_with_noop_exporter_replaces_global_provider() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_EXPORTER", "noop")]);
+
+        assert_reinstall_replaces_global_provider();
+    }
+
+    #[test]
+    fn reinstall_with_tracing_disabled_replaces_global_provider() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_ENABLED", "false")]);
+
+        assert_reinstall_replaces_global_provider();
+    }
+
+    #[test]
+    fn caches_the_service_identity_at_install() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[
+            ("TRACES_EXPORTER", "noop"),
+            ("TRACES_SERVICE_NAME", "checkout"),
+        ]);
+        let app_cfgs = AppConfigs::new();
+
+        let provider = install().expect("install");
+
+        assert_eq!(
+            service_identity(),
+            Some(ServiceIdentity {
+                name: "checkout".to_string(),
+                namespace: format!("{}", app_cfgs.namespace),
+                env: format!("{}", app_cfgs.env),
+            })
+        );
+        shutdown(&provider).expect("shutdown");
+    }
+
+    #[cfg(feature = "otlp")]
+    #[test]
+    fn noop_policy_skips_a_missing_endpoint() {
+        assert!(skips_endpoint(FailurePolicy::Noop, ""));
+        assert!(skips_endpoint(FailurePolicy::Noop, "  "));
+        assert!(!skips_endpoint(
+            FailurePolicy::Noop,
+            "http://localhost:4317"
+        ));
+    }
+
+    #[cfg(feature = "otlp")]
+    #[test]
+    fn fail_policy_fails_on_a_missing_endpoint() {
+        assert!(!skips_endpoint(FailurePolicy::Fail, ""));
+        assert!(!skips_endpoint(
+            FailurePolicy::Fail,
+            "http://localhost:4317"
+        ));
+
+        // The OTLP gRPC exporter is installed and rejects the empty endpoint
+        assert!(matches!(
+            exporters::otlp_grpc::Builder::new().endpoint("").build(),
+            Err(TracesError::InvalidConfigError(_))
+        ));
+    }
+
+    #[test]
+    fn selects_the_configured_exporter() {
+        assert_eq!(selected_exporter(ExporterKind::Auto), ExporterKind::Auto);
+        assert_eq!(selected_exporter(ExporterKind::Noop), ExporterKind::Noop);
+    }
+
+    #[test]
+    fn selects_the_compiled_in_exporters() {
+        let stdout = if cfg!(feature = "stdout") {
+            ExporterKind::Stdout
+        } else {
+            ExporterKind::Auto
+        };
+        let otlp = if cfg!(any(feature = "otlp", feature = "otlp-http")) {
+            ExporterKind::Otlp
+        } else {
+            ExporterKind::Auto
+        };
+
+        assert_eq!(selected_exporter(ExporterKind::Stdout), stdout);
+        assert_eq!(selected_exporter(ExporterKind::Otlp), otlp);
+    }
+
+    #[cfg(not(feature = "stdout"))]
+    #[test]
+    fn falls_back_to_auto_when_the_exporter_is_not_compiled_in() {
+        assert_eq!(selected_exporter(ExporterKind::Stdout), ExporterKind::Auto);
+    }
+
+    #[cfg(not(any(feature = "stdout", feature = "otlp", feature = "otlp-http")))]
+    #[test]
+    fn installs_the_default_exporter_when_the_exporter_is_not_compiled_in() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_EXPORTER", "stdout")]);
+
+        let provider = install().expect("default exporter installed");
+
+        shutdown(&provider).expect("shutdown");
+    }
+
+    #[test]
+    fn parses_the_exporter_case_insensitively() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_EXPORTER", " NOOP ")]);
+
+        assert_eq!(TracesConfigs::new().exporter, ExporterKind::Noop);
+    }
+
+    /// A generator returning the same trace ID, to tell the spans of two providers apart.
+    #[derive(Debug)]
+    struct ConstantTraceIdGenerator(u128);
+
+    impl IdGenerator for ConstantTraceIdGenerator {
+        fn new_trace_id(&self) -> TraceId {
+            TraceId::from(self.0)
+        }
+
+        fn new_span_id(&self) -> SpanId {
+            SpanId::from(1_u64)
+        }
+    }
+
+    #[test]
+    fn reinstall_shuts_down_and_replaces_the_previous_provider() {
+        let _lock = test_utils::lock();
+        let _env = test_utils::set_env(&[("TRACES_ENABLED", "false")]);
+
+        let first = install_with_id_generator(ConstantTraceIdGenerator(1)).expect("first install");
+        let second =
+            install_with_id_generator(ConstantTraceIdGenerator(2)).expect("second install");
+
+        // Spans of the noop provider are dropped but keep the identifiers it generated
+        let ctx = helpers::ctx(&tracer(), SpanKind::Internal, "after reinstall");
+        assert_eq!(ctx.span().span_context().trace_id(), TraceId::from(2_u128));
+        assert_eq!(shutdown(&first), Err(TracesError::AlreadyShutdownError));
+        shutdown(&second).expect("second provider still running");
+    }
+
+    /// An exporter taking `delay` to export each batch.
+    #[derive(Debug)]
+    struct SlowExporter {
+        delay: Duration,
+    }
+
+    impl SpanExporter for SlowExporter {
+        async fn export(&self, _batch: Vec<SpanData>) -> OTelSdkResult {
+            thread::sleep(self.delay);
+            Ok(())
+        }
+    }
+
+    /// Builds a provider exporting in batches through a `SlowExporter`, with a span pending.
+    fn pending_provider(delay: Duration) -> SdkTracerProvider {
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(SlowExporter { delay })
+            .build();
+        provider.tracer("flush").in_span("pending", |_| {});
+
+        provider
+    }
+
+    #[test]
+    fn flush_with_timeout_returns_once_flushed() {
+        let provider = pending_provider(Duration::ZERO);
+
+        assert_eq!(
+            flush_with_timeout(&provider, Duration::from_secs(5)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn flush_with_timeout_gives_up_after_the_timeout() {
+        let provider = pending_provider(Duration::from_secs(2));
+        let timeout = Duration::from_millis(50);
+
+        let started = Instant::now();
+        let result = flush_with_timeout(&provider, timeout);
+
+        assert_eq!(result, Err(TracesError::FlushTimeoutError));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn flush_with_timeout_reports_a_shut_down_provider() {
+        let provider = pending_provider(Duration::ZERO);
+        provider.shutdown().expect("shutdown");
+
+        assert_eq!(
+            flush_with_timeout(&provider, Duration::from_secs(5)),
+            Err(TracesError::AlreadyShutdownError)
+        );
+    }
+
+    /// Ends a span started by `tracer` on the global pipeline and returns it.
+    fn global_span(pipeline: &test_utils::Pipeline, tracer: BoxedTracer) -> SpanData {
+        global::set_tracer_provider(pipeline.provider.clone());
+        tracer.in_span("scoped", |_| {});
+
+        pipeline.spans().pop().expect("exported span")
+    }
+
+    #[test]
+    fn tracer_is_scoped_to_the_service() {
+        let _lock = test_utils::lock();
+        let pipeline = test_utils::pipeline();
+
+        let span = global_span(&pipeline, tracer());
+
+        assert_eq!(span.instrumentation_scope.name(), tracer_name());
+        assert_eq!(
+            span.instrumentation_scope.version(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn tracer_with_scope_uses_the_provided_scope() {
+        let _lock = test_utils::lock();
+        let pipeline = test_utils::pipeline();
+
+        let span = global_span(&pipeline, tracer_with_scope("payments", "1.2.3"));
+
+        assert_eq!(span.instrumentation_scope.name(), "payments");
+        assert_eq!(span.instrumentation_scope.version(), Some("1.2.3"));
+    }
 }
@@ -0,0 +1,193 @@
+// Copyright (c) 2025, The Ruskit Authors
+// MIT License
+// All rights reserved.
+
+//! Helpers shared by the unit tests.
+
+use opentelemetry::{global::BoxedTracer, trace::TracerProvider};
+use opentelemetry_sdk::{
+    Resource,
+    error::OTelSdkResult,
+    trace::{
+        InMemorySpanExporter, SdkTracerProvider, SpanData, SpanExporter, TracerProviderBuilder,
+    },
+};
+use std::{
+    env,
+    ffi::OsString,
+    pin::pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+/// Guards the process-wide state changed by tests.
+static GLOBAL_STATE: Mutex<()> = Mutex::new(());
+
+/// Serializes the tests changing process-wide state: the global tracer provider and
+/// propagator, and the environment variables read by the configuration.
+pub(crate) fn lock() -> MutexGuard<'static, ()> {
+    GLOBAL_STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Environment variables set by a test, restored when dropped.
+pub(crate) struct EnvGuard(Vec<(String, Option<OsString>)>);
+
+/// Sets environment variables until the returned guard is dropped.
+///
+/// The caller must hold the `lock` guard for as long as the variables are set.
+pub(crate) fn set_env(vars: &[(&str, &str)]) -> EnvGuard {
+    let previous = vars
+        .iter()
+        .map(|(key, value)| {
+            let previous = env::var_os(key);
+            // SAFETY: tests changing the environment are serialized by `lock`
+            unsafe { env::set_var(key, value) };
+            (key.to_string(), previous)
+        })
+        .collect();
+
+    EnvGuard(previous)
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (key, previous) in self.0.drain(..) {
+            // SAFETY: tests changing the environment are serialized by `lock`
+            unsafe {
+                match previous {
+                    Some(value) => env::set_var(&key, value),
+                    None => env::remove_var(&key),
+                }
+            }
+        }
+    }
+}
+
+/// Runs a future to completion on the current thread, polling it until it is ready.
+///
+/// Only meant for futures that do not wait on I/O or timers, e.g. mock services.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::yield_now();
+    }
+}
+
+/// A local tracer provider capturing its finished spans in memory.
+pub(crate) struct Pipeline {
+    pub(crate) provider: SdkTracerProvider,
+    pub(crate) tracer: BoxedTracer,
+    exporter: InMemorySpanExporter,
+}
+
+impl Pipeline {
+    /// Returns the spans finished so far, in end order.
+    pub(crate) fn spans(&self) -> Vec<SpanData> {
+        self.exporter.get_finished_spans().unwrap_or_default()
+    }
+}
+
+/// Builds a local pipeline with the default SDK settings, sampling every span.
+pub(crate) fn pipeline() -> Pipeline {
+    pipeline_with(SdkTracerProvider::builder())
+}
+
+/// Builds a local pipeline from a provider builder, exporting each span when it ends.
+pub(crate) fn pipeline_with(builder: TracerProviderBuilder) -> Pipeline {
+    let exporter = InMemorySpanExporter::default();
+    let provider = builder.with_simple_exporter(exporter.clone()).build();
+    let tracer = BoxedTracer::new(Box::new(provider.tracer("test")));
+
+    Pipeline {
+        provider,
+        tracer,
+        exporter,
+    }
+}
+
+/// An exporter keeping the resource set by the provider.
+#[derive(Debug, Clone, Default)]
+struct ResourceExporter(Arc<Mutex<Option<Resource>>>);
+
+impl SpanExporter for ResourceExporter {
+    async fn export(&self, _batch: Vec<SpanData>) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        *self.0.lock().unwrap() = Some(resource.clone());
+    }
+}
+
+/// Builds a provider from a provider builder and returns the resource it exports with.
+pub(crate) fn exported_resource(builder: TracerProviderBuilder) -> Resource {
+    let exporter = ResourceExporter::default();
+    let _provider = builder.with_simple_exporter(exporter.clone()).build();
+
+    let resource = exporter.0.lock().unwrap().take();
+    resource.expect("resource set by the provider")
+}
+
+/// Registers the W3C trace context and baggage propagators as the global propagator.
+///
+/// The caller must hold the `lock` guard.
+#[cfg(any(
+    feature = "otlp",
+    feature = "http",
+    feature = "kafka",
+    feature = "nats"
+))]
+pub(crate) fn install_propagators() {
+    use crate::propagation::{self, PropagatorKind};
+
+    propagation::install(&[PropagatorKind::TraceContext, PropagatorKind::Baggage]);
+}
+
+/// Starts a sampled producer span on the pipeline, with a `tenant.id` baggage entry.
+#[cfg(any(
+    feature = "otlp",
+    feature = "http",
+    feature = "kafka",
+    feature = "nats"
+))]
+pub(crate) fn sent_context(pipeline: &Pipeline) -> opentelemetry::Context {
+    use crate::helpers;
+    use opentelemetry::trace::SpanKind;
+
+    let ctx = helpers::ctx(&pipeline.tracer, SpanKind::Producer, "send");
+
+    helpers::set_baggage(&ctx, "tenant.id", "acme")
+}
+
+/// Asserts the received context continues the trace of the sent one, with its baggage.
+#[cfg(any(
+    feature = "otlp",
+    feature = "http",
+    feature = "kafka",
+    feature = "nats"
+))]
+pub(crate) fn assert_propagated(sent: &opentelemetry::Context, received: &opentelemetry::Context) {
+    use crate::helpers;
+    use opentelemetry::trace::TraceContextExt;
+
+    let sent_span = sent.span();
+    let received_span = received.span();
+    let (sent_span, received_span) = (sent_span.span_context(), received_span.span_context());
+
+    assert!(received_span.is_remote());
+    assert!(received_span.is_sampled());
+    assert_eq!(received_span.trace_id(), sent_span.trace_id());
+    assert_eq!(received_span.span_id(), sent_span.span_id());
+    assert_eq!(
+        helpers::get_baggage(received, "tenant.id").as_deref(),
+        Some("acme")
+    );
+}